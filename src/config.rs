@@ -1,8 +1,13 @@
 extern crate directories;
 
-use std::{error::Error, fmt::Display, path::PathBuf};
+use std::{collections::BTreeMap, error::Error, fmt::Display, path::PathBuf};
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use time::{format_description::well_known::Iso8601, Date, Duration, OffsetDateTime};
+use url::Url;
+
+use crate::libminiflux::{EntrySort, ReadStatus};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ThemeConfig {
@@ -10,6 +15,10 @@ pub struct ThemeConfig {
 	pub unread_color : String,
 	#[serde(default = "ThemeConfig::default_read_color")]
 	pub read_color : String,
+	#[serde(default = "ThemeConfig::default_selected_color")]
+	pub selected_color : String,
+	#[serde(default = "ThemeConfig::default_highlight_color")]
+	pub highlight_color : String,
 }
 impl ThemeConfig {
 	pub fn default_unread_color() -> String {
@@ -18,6 +27,12 @@ impl ThemeConfig {
 	pub fn default_read_color() -> String {
 		tuirealm::props::Color::Gray.to_string()
 	}
+	pub fn default_selected_color() -> String {
+		tuirealm::props::Color::DarkGray.to_string()
+	}
+	pub fn default_highlight_color() -> String {
+		tuirealm::props::Color::Yellow.to_string()
+	}
 }
 
 impl Default for ThemeConfig {
@@ -25,24 +40,187 @@ impl Default for ThemeConfig {
 		ThemeConfig {
 			unread_color: ThemeConfig::default_unread_color(),
 			read_color: ThemeConfig::default_read_color(),
+			selected_color: ThemeConfig::default_selected_color(),
+			highlight_color: ThemeConfig::default_highlight_color(),
 		}
 	}
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// A saved search from a `[[views]]` table, which shows up as its own view in the entry list after
+/// the built-in ones
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedView {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReadStatus>,
+    #[serde(default)]
+    pub starred: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feed_ids: Vec<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub category_ids: Vec<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    // Either a date like "2024-01-31", or a number of days ago like "7d"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_after: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<EntrySort>,
+}
+impl SavedView {
+    pub fn published_after_time(&self, now: OffsetDateTime) -> Result<Option<OffsetDateTime>, InvalidSavedViewError> {
+        let Some(published_after) = &self.published_after else {
+            return Ok(None);
+        };
+        let invalid = || InvalidSavedViewError {
+            name: self.name.clone(),
+            reason: format!(
+                "published_after should be a date like \"2024-01-31\" or a number of days like \"7d\", not \"{}\"",
+                published_after
+            )
+        };
+        if let Some(days) = published_after.strip_suffix('d') {
+            let days = days.trim().parse::<i64>().map_err(|_| invalid())?;
+            if days < 0 {
+                return Err(InvalidSavedViewError {
+                    name: self.name.clone(),
+                    reason: format!("published_after can't be a negative number of days (\"{}\")", published_after)
+                });
+            }
+            // Checked, since a big enough number of days would otherwise overflow and panic
+            return i32::try_from(days).ok()
+                .and_then(|days| Duration::DAY.checked_mul(days))
+                .and_then(|duration| now.checked_sub(duration))
+                .map(Some)
+                .ok_or_else(|| InvalidSavedViewError {
+                    name: self.name.clone(),
+                    reason: format!("published_after is too far back (\"{}\")", published_after)
+                });
+        }
+        let date = Date::parse(published_after, &Iso8601::DATE).map_err(|_| invalid())?;
+        return Ok(Some(date.midnight().assume_utc()));
+    }
+}
+
+/// A `[[filters]]` rule, which hides, highlights, or marks as read any entry whose title, feed, or
+/// url matches a regex
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FilterRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub field: FilterField,
+    pub action: FilterAction,
+}
+impl FilterRule {
+    pub fn regex(&self) -> Result<Regex, InvalidFilterRuleError> {
+        return Regex::new(&self.pattern).map_err(|e| InvalidFilterRuleError {
+            pattern: self.pattern.clone(),
+            reason: e.to_string()
+        });
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterField {
+    #[default]
+    Title,
+    Feed,
+    Url,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterAction {
+    Hide,
+    Highlight,
+    MarkRead,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Credentials {
+    ApiKey(String),
+    Basic { username: String, password: String },
+}
+
+// Server urls starting with this are treated as a path to a Unix socket that Miniflux is listening on
+pub const UNIX_SOCKET_URL_PREFIX: &str = "unix://";
+
+// The environment variable that, when set, overrides whatever API key is configured in config.toml
+pub const API_KEY_ENV_VAR: &str = "CLIFLUX_API_KEY";
+
+// Headers that cliflux sets itself for authentication, which [headers] isn't allowed to override
+const RESERVED_HEADER_NAMES: [&str; 2] = ["X-Auth-Token", "Authorization"];
+
+// The service name used when looking up the API key in the OS keyring
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE_NAME: &str = "cliflux";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
-    pub api_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_command: Option<String>,
+    #[serde(default)]
+    pub use_keyring: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
     pub server_url: String,
     #[serde(default)]
     pub allow_invalid_certs: bool,
     #[serde(default)]
     pub use_rustls: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert_password: Option<String>,
+    #[serde(default = "Config::default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    #[serde(default = "Config::default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    #[serde(default = "Config::default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default = "Config::default_stale_feed_days")]
+    pub stale_feed_days: u32,
+    #[serde(default)]
+    pub skip_confirmations: bool,
+    // 0 means "never"
+    #[serde(default)]
+    pub auto_refresh_secs: u64,
+    #[serde(default)]
+    pub sort: EntrySort,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<SavedView>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<FilterRule>,
 	#[serde(default)]
 	pub theme : ThemeConfig,
 }
 impl Display for Config {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		let stringified = match toml::to_string(self) {
+		// Never print the actual API key, so that the output is safe to paste into bug reports
+		let redacted = Config {
+			api_key: self.api_key.as_ref().map(|_| "<redacted>".to_string()),
+			password: self.password.as_ref().map(|_| "<redacted>".to_string()),
+			client_cert_password: self.client_cert_password.as_ref().map(|_| "<redacted>".to_string()),
+			// Extra headers are usually there to get past an auth proxy, so treat them as secrets too
+			headers: self.headers.keys()
+				.map(|name| (name.clone(), "<redacted>".to_string()))
+				.collect(),
+			..self.clone()
+		};
+		let stringified = match toml::to_string(&redacted) {
 			Ok(s) => s,
 			Err(e) => e.to_string()
 		};
@@ -51,36 +229,317 @@ impl Display for Config {
 }
 
 impl Config {
+    pub fn default_connect_timeout_secs() -> u64 {
+        10
+    }
+    pub fn default_request_timeout_secs() -> u64 {
+        30
+    }
+    pub fn default_max_retries() -> u32 {
+        3
+    }
+    pub fn default_stale_feed_days() -> u32 {
+        30
+    }
+
     pub fn from_file(path: &PathBuf) -> Result<Config, Box<dyn std::error::Error>> {
         let file_contents = std::fs::read_to_string(path)?;
         let parsed_result = toml::from_str::<Config>(&file_contents)?;
-        let cleaned_server_url = Config::validate_and_clean_server_url(parsed_result.server_url)?;
-        return Ok(Config {
+        let cleaned_server_url = Config::validate_and_clean_server_url(parsed_result.server_url.clone())?;
+        // If we're using a username/password, leave api_key as-is so that credentials() can 
+        // complain if both were configured. The other places an API key can come from aren't
+        // looked at by credentials(), so they're checked here instead.
+        let api_key = match parsed_result.username.as_deref().filter(|username| !username.trim().is_empty()) {
+            Some(_) => {
+                if let Some(source) = parsed_result.other_api_key_source() {
+                    return Err(Box::new(InvalidCredentialsError {
+                        reason: format!("both {} and username are set; use one or the other", source)
+                    }));
+                }
+                parsed_result.api_key.clone()
+            },
+            None => Some(parsed_result.resolve_api_key()?)
+        };
+        let config = Config {
             server_url: cleaned_server_url,
+            api_key,
             ..parsed_result
-        });
+        };
+        config.credentials()?;
+        config.validate_headers()?;
+        for view in &config.views {
+            view.published_after_time(OffsetDateTime::now_utc())?;
+        }
+        for rule in &config.filters {
+            rule.regex()?;
+        }
+        return Ok(config);
+    }
+
+    /// Makes sure [headers] doesn't try to set the headers cliflux uses for authentication, since
+    /// those would silently replace the configured credentials.
+    pub fn validate_headers(&self) -> Result<(), ReservedHeaderError> {
+        for name in self.headers.keys() {
+            if RESERVED_HEADER_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(name.trim())) {
+                return Err(ReservedHeaderError { name: name.to_string() });
+            }
+        }
+        return Ok(());
+    }
+
+    /// Decides which authentication scheme to use, based on which of api_key and username/password
+    /// are set.
+    pub fn credentials(&self) -> Result<Credentials, InvalidCredentialsError> {
+        let non_empty = |value: &Option<String>| {
+            value.as_ref()
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| v.to_string())
+        };
+        match (non_empty(&self.api_key), non_empty(&self.username), non_empty(&self.password)) {
+            (Some(_), Some(_), _) => Err(InvalidCredentialsError {
+                reason: "both api_key and username are set; use one or the other".to_string()
+            }),
+            (Some(api_key), None, _) => Ok(Credentials::ApiKey(api_key)),
+            (None, Some(username), Some(password)) => Ok(Credentials::Basic { username, password }),
+            (None, Some(_), None) => Err(InvalidCredentialsError {
+                reason: "username is set, but password is missing".to_string()
+            }),
+            (None, None, _) => Err(InvalidCredentialsError {
+                reason: "neither api_key nor username/password are set".to_string()
+            }),
+        }
     }
 
-    fn validate_and_clean_server_url(url: String) -> Result<String, InvalidServerUrlError> {
-        if url.trim().is_empty() {
-            return Err(InvalidServerUrlError { value: url });
+    /// Checks that the server url is something we can connect to (an http://, https://, or unix:// 
+    /// url), and strips any trailing slash so that we can safely append API paths to it.
+    pub fn validate_and_clean_server_url(url: String) -> Result<String, InvalidServerUrlError> {
+        let invalid = |reason: &str| InvalidServerUrlError { 
+            value: url.clone(), 
+            reason: reason.to_string() 
+        };
+        let trimmed = url.trim();
+        if trimmed.is_empty() {
+            return Err(invalid("it's empty"));
         }
 
-        if url.ends_with("/") {
-            return Ok(url.strip_suffix("/").unwrap().to_string());
+        if let Some(socket_path) = trimmed.strip_prefix(UNIX_SOCKET_URL_PREFIX) {
+            if !cfg!(unix) {
+                return Err(invalid("unix:// urls are only supported on Unix-like systems"));
+            }
+            if socket_path.is_empty() || !socket_path.starts_with('/') {
+                return Err(invalid("unix:// urls need an absolute path to the socket, like unix:///run/miniflux.sock"));
+            }
+            return Ok(trimmed.to_string());
+        }
+
+        let parsed = match Url::parse(trimmed) {
+            Ok(parsed) => parsed,
+            Err(url::ParseError::RelativeUrlWithoutBase) => 
+                return Err(invalid("it needs to start with http://, https://, or unix://")),
+            Err(e) => return Err(invalid(&e.to_string()))
+        };
+        if parsed.scheme() != "http" && parsed.scheme() != "https" {
+            return Err(invalid("it needs to start with http://, https://, or unix://"));
+        }
+        if parsed.host_str().unwrap_or_default().is_empty() {
+            return Err(invalid("it doesn't have a hostname"));
+        }
+        if parsed.query().is_some() || parsed.fragment().is_some() {
+            return Err(invalid("it shouldn't have a query string or #fragment"));
+        }
+        return Ok(parsed.as_str().trim_end_matches('/').to_string());
+    }
+
+    /// Renders this config as TOML, with comments explaining each option, for writing out a new 
+    /// config file.
+    pub fn to_commented_toml(&self) -> String {
+        let quoted = |value: &str| toml::Value::String(value.to_string()).to_string();
+        let mut result = String::new();
+        result += "# cliflux configuration file\n";
+        result += "# See https://github.com/spencerwi/cliflux#initial-setup for all available options.\n\n";
+        result += "# The base URL of your Miniflux server, including the port if it isn't the default,\n";
+        result += "# or unix:///path/to/miniflux.sock if Miniflux is listening on a Unix socket\n";
+        result += &format!("server_url = {}\n\n", quoted(&self.server_url));
+        result += "# Your Miniflux API key, from Settings > API Keys > Create a new API key.\n";
+        result += "# To keep it out of this file, remove it and either set api_key_command (for example,\n";
+        result += "# api_key_command = \"pass show miniflux\") or the CLIFLUX_API_KEY environment variable.\n";
+        match &self.api_key {
+            Some(api_key) => result += &format!("api_key = {}\n\n", quoted(api_key)),
+            None => result += "# api_key = \"\"\n\n"
+        }
+        result += "# Skip TLS certificate verification, for servers with self-signed certificates\n";
+        result += &format!("allow_invalid_certs = {}\n\n", self.allow_invalid_certs);
+        result += "# Use rustls instead of your OS's native TLS library (for example, if your server requires TLS 1.3)\n";
+        result += &format!("use_rustls = {}\n\n", self.use_rustls);
+        result += "# A PEM bundle of extra CA certificates to trust, for servers behind a private CA\n";
+        result += "# ca_cert_path = \"/path/to/ca.pem\"\n\n";
+        result += "# A client certificate for mutual TLS: either PEM (with the PKCS#8 key in client_key_path,\n";
+        result += "# or in the same file), or PKCS#12 (.p12/.pfx, with client_cert_password if it has one)\n";
+        result += "# client_cert_path = \"/path/to/client.pem\"\n";
+        result += "# client_key_path = \"/path/to/client.key\"\n\n";
+        result += "# How long to wait (in seconds) to connect to the server, and for each request to finish; 0 means forever\n";
+        result += &format!("connect_timeout_secs = {}\n", self.connect_timeout_secs);
+        result += &format!("request_timeout_secs = {}\n\n", self.request_timeout_secs);
+        result += "# How many times to retry a failed fetch (with exponential backoff) before giving up\n";
+        result += &format!("max_retries = {}\n\n", self.max_retries);
+        result += "# An HTTP, HTTPS, or SOCKS5 proxy to connect through, for example \"socks5://localhost:1080\"\n";
+        match &self.proxy {
+            Some(proxy) => result += &format!("proxy = {}\n\n", quoted(proxy)),
+            None => result += "# proxy = \"http://proxy.example.com:8080\"\n\n"
+        }
+        result += "# Extra HTTP headers to send with every request, for example to get through an auth proxy\n";
+        result += "# [headers]\n";
+        result += "# \"CF-Access-Client-Id\" = \"...\"\n";
+        result += "# \"CF-Access-Client-Secret\" = \"...\"\n\n";
+        result += "# Feeds with no new entries in this many days are listed in the feed health view; 0 means never\n";
+        result += &format!("stale_feed_days = {}\n\n", self.stale_feed_days);
+        result += "# Skip the \"Are you sure?\" prompt before marking everything as read, unsubscribing, and so on\n";
+        result += &format!("skip_confirmations = {}\n\n", self.skip_confirmations);
+        result += "# Reload the entry list in the background every this many seconds; 0 means never\n";
+        result += &format!("auto_refresh_secs = {}\n\n", self.auto_refresh_secs);
+        result += "[sort]\n";
+        result += "# What to sort the entry list by: \"published_at\", \"feed\", \"category\", or \"status\"\n";
+        result += &format!("order = {}\n", quoted(&self.sort.order.to_string()));
+        result += "# \"asc\" or \"desc\"\n";
+        result += &format!("direction = {}\n\n", quoted(&self.sort.direction.to_string()));
+        result += "[theme]\n";
+        result += "# Any color name, 0-255 color index, or #RRGGBB hex value\n";
+        result += &format!("unread_color = {}\n", quoted(&self.theme.unread_color));
+        result += &format!("read_color = {}\n", quoted(&self.theme.read_color));
+        result += "# The background color of entries that are selected for a bulk action\n";
+        result += &format!("selected_color = {}\n", quoted(&self.theme.selected_color));
+        result += "# The color of entries picked out by a \"highlight\" filter rule\n";
+        result += &format!("highlight_color = {}\n", quoted(&self.theme.highlight_color));
+        result += "\n";
+        result += "# Saved searches, which show up as extra views when switching views with `v`\n";
+        result += "# [[views]]\n";
+        result += "# name = \"This week in Rust\"\n";
+        result += "# status = \"unread\" # or \"read\"; leave it out for both\n";
+        result += "# starred = false\n";
+        result += "# feed_ids = [1, 2]\n";
+        result += "# category_ids = [3]\n";
+        result += "# search = \"rust\"\n";
+        result += "# published_after = \"7d\" # or a date, like \"2024-01-31\"\n";
+        result += "# sort = { order = \"feed\", direction = \"asc\" }\n";
+        result += "\n";
+        result += "# Rules for hiding, highlighting, or automatically marking as read entries that match a regex\n";
+        result += "# [[filters]]\n";
+        result += "# pattern = \"(?i)sponsored\"\n";
+        result += "# field = \"title\" # or \"feed\", or \"url\"\n";
+        result += "# action = \"hide\" # or \"highlight\", or \"mark_read\"\n";
+        return result;
+    }
+
+    /// Figures out the API key to use, in order of precedence: the CLIFLUX_API_KEY environment 
+    /// variable, the output of `api_key_command`, the OS keyring (if `use_keyring` is set), and 
+    /// finally the plaintext `api_key`.
+    // Which of the ways of supplying an API key besides api_key itself is in use, if any
+    fn other_api_key_source(&self) -> Option<String> {
+        if std::env::var(API_KEY_ENV_VAR).is_ok_and(|key| !key.trim().is_empty()) {
+            return Some(format!("the {} environment variable", API_KEY_ENV_VAR));
+        }
+        if self.api_key_command.is_some() {
+            return Some("api_key_command".to_string());
         }
+        if self.use_keyring {
+            return Some("use_keyring".to_string());
+        }
+        return None;
+    }
+
+    fn resolve_api_key(&self) -> Result<String, Box<dyn std::error::Error>> {
+        if let Ok(key) = std::env::var(API_KEY_ENV_VAR) {
+            if !key.trim().is_empty() {
+                return Ok(key.trim().to_string());
+            }
+        }
+
+        if let Some(command) = &self.api_key_command {
+            return Config::run_api_key_command(command);
+        }
+
+        if self.use_keyring {
+            return self.read_api_key_from_keyring();
+        }
+
+        match &self.api_key {
+            Some(key) if !key.trim().is_empty() => Ok(key.to_string()),
+            _ => Err(Box::new(MissingApiKeyError))
+        }
+    }
+
+    fn run_api_key_command(command: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let output = 
+            if cfg!(target_os = "windows") {
+                std::process::Command::new("cmd").args(["/C", command]).output()
+            } else {
+                std::process::Command::new("sh").args(["-c", command]).output()
+            }
+            .map_err(|e| ApiKeyCommandError { 
+                command: command.to_string(), 
+                reason: e.to_string() 
+            })?;
+
+        if !output.status.success() {
+            return Err(Box::new(ApiKeyCommandError {
+                command: command.to_string(),
+                reason: format!(
+                    "exited with {}: {}", 
+                    output.status, 
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )
+            }));
+        }
+
+        // Tools like `pass` print the secret on the first line, with other metadata after it
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        match stdout.lines().next().map(str::trim) {
+            Some(key) if !key.is_empty() => Ok(key.to_string()),
+            _ => Err(Box::new(ApiKeyCommandError {
+                command: command.to_string(),
+                reason: "command produced no output".to_string()
+            }))
+        }
+    }
+
+    #[cfg(feature = "keyring")]
+    fn read_api_key_from_keyring(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE_NAME, &self.server_url)?;
+        return Ok(entry.get_password()?);
+    }
 
-        return Ok(url);
+    #[cfg(not(feature = "keyring"))]
+    fn read_api_key_from_keyring(&self) -> Result<String, Box<dyn std::error::Error>> {
+        return Err(Box::new(KeyringNotSupportedError));
     }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
-            api_key: "FIXME".to_string(),
+            api_key: Some("FIXME".to_string()),
+            api_key_command: None,
+            use_keyring: false,
+            username: None,
+            password: None,
             server_url: "FIXME".to_string(),
             allow_invalid_certs: false,
             use_rustls: false,
+            ca_cert_path: None,
+            client_cert_path: None,
+            client_key_path: None,
+            client_cert_password: None,
+            connect_timeout_secs: Config::default_connect_timeout_secs(),
+            request_timeout_secs: Config::default_request_timeout_secs(),
+            max_retries: Config::default_max_retries(),
+            proxy: None,
+            headers: BTreeMap::new(),
+            stale_feed_days: Config::default_stale_feed_days(),
+            skip_confirmations: false,
+            auto_refresh_secs: 0,
+            sort: EntrySort::default(),
+            views: Vec::new(),
+            filters: Vec::new(),
 			theme: ThemeConfig::default()
         }
     }
@@ -109,14 +568,94 @@ impl Error for ConfigFileAlreadyExistsError {}
 #[derive(Debug, Clone)]
 pub struct InvalidServerUrlError {
     value: String,
+    reason: String,
 }
 impl Display for InvalidServerUrlError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Invalid server url: \"{}\"", self.value)
+        write!(f, "Invalid server url \"{}\": {}", self.value, self.reason)
     }
 }
 impl Error for InvalidServerUrlError {}
 
+#[derive(Debug, Clone)]
+pub struct MissingApiKeyError;
+impl Display for MissingApiKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "No API key configured: set one of api_key, api_key_command, or use_keyring, or set the {} environment variable", API_KEY_ENV_VAR)
+    }
+}
+impl Error for MissingApiKeyError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidCredentialsError {
+    reason: String,
+}
+impl Display for InvalidCredentialsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid credentials configuration: {}", self.reason)
+    }
+}
+impl Error for InvalidCredentialsError {}
+
+#[derive(Debug, Clone)]
+pub struct ReservedHeaderError {
+    name: String,
+}
+impl Display for ReservedHeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The \"{}\" entry in [headers] would replace your credentials; use api_key or username/password instead", self.name)
+    }
+}
+impl Error for ReservedHeaderError {}
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyCommandError {
+    command: String,
+    reason: String,
+}
+impl Display for ApiKeyCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "api_key_command \"{}\" failed: {}", self.command, self.reason)
+    }
+}
+impl Error for ApiKeyCommandError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidSavedViewError {
+    name: String,
+    reason: String,
+}
+impl Display for InvalidSavedViewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The \"{}\" view in your config file isn't valid: {}", self.name, self.reason)
+    }
+}
+impl Error for InvalidSavedViewError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidFilterRuleError {
+    pattern: String,
+    reason: String,
+}
+impl Display for InvalidFilterRuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The filter rule \"{}\" in your config file isn't a valid regex: {}", self.pattern, self.reason)
+    }
+}
+impl Error for InvalidFilterRuleError {}
+
+#[cfg(not(feature = "keyring"))]
+#[derive(Debug, Clone)]
+pub struct KeyringNotSupportedError;
+#[cfg(not(feature = "keyring"))]
+impl Display for KeyringNotSupportedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "use_keyring is set, but this build of cliflux was compiled without the \"keyring\" feature")
+    }
+}
+#[cfg(not(feature = "keyring"))]
+impl Error for KeyringNotSupportedError {}
+
 pub fn get_config_file_path() -> Result<PathBuf, CannotFindConfigDirError> {
     let path = directories::ProjectDirs::from("com", "spencerwi", "cliflux").map(|project_dirs| {
         let mut config_path = project_dirs.config_dir().to_owned();
@@ -129,18 +668,120 @@ pub fn get_config_file_path() -> Result<PathBuf, CannotFindConfigDirError> {
     }
 }
 
-pub fn init() -> Result<PathBuf, Box<dyn std::error::Error>> {
+/// Returns the path that a new config file should be written to, or an error if there's already 
+/// one there.
+pub fn new_config_file_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
     let config_file_path = get_config_file_path()?;
     if config_file_path.exists() {
         return Err(Box::new(ConfigFileAlreadyExistsError {
             path: config_file_path.to_str().unwrap().to_string(),
         }));
     }
+    return Ok(config_file_path);
+}
 
+pub fn init(config: &Config) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let config_file_path = new_config_file_path()?;
     std::fs::create_dir_all(config_file_path.parent().unwrap())?;
     std::fs::write(
         &config_file_path,
-        toml::to_string(&Config::default())?,
+        config.to_commented_toml(),
     )?;
     return Ok(config_file_path);
 }
+
+#[cfg(test)]
+mod tests {
+    use time::Month;
+
+    use super::*;
+
+    fn view_published_after(published_after: &str) -> SavedView {
+        return toml::from_str::<SavedView>(
+            &format!("name = \"Test\"\npublished_after = {}", toml::Value::String(published_after.to_string()))
+        ).unwrap();
+    }
+
+    fn now() -> OffsetDateTime {
+        return Date::from_calendar_date(2024, Month::March, 15).unwrap().midnight().assume_utc();
+    }
+
+    #[test]
+    fn published_after_takes_a_number_of_days_ago() {
+        assert_eq!(
+            view_published_after("7d").published_after_time(now()).unwrap(),
+            Some(Date::from_calendar_date(2024, Month::March, 8).unwrap().midnight().assume_utc())
+        );
+    }
+
+    #[test]
+    fn published_after_takes_a_date() {
+        assert_eq!(
+            view_published_after("2024-01-31").published_after_time(now()).unwrap(),
+            Some(Date::from_calendar_date(2024, Month::January, 31).unwrap().midnight().assume_utc())
+        );
+    }
+
+    #[test]
+    fn published_after_rejects_negative_days() {
+        assert!(view_published_after("-1d").published_after_time(now()).is_err());
+    }
+
+    #[test]
+    fn published_after_rejects_day_counts_too_big_to_subtract() {
+        // Too big for the multiplication, and big enough to go past the earliest representable date
+        assert!(view_published_after("99999999999999d").published_after_time(now()).is_err());
+        assert!(view_published_after("2000000000d").published_after_time(now()).is_err());
+    }
+
+    #[test]
+    fn published_after_rejects_anything_else() {
+        for garbage in ["", "last week", "d", "sevend", "2024-13-01", "31/01/2024"] {
+            assert!(view_published_after(garbage).published_after_time(now()).is_err(), "{:?}", garbage);
+        }
+    }
+
+    fn clean(url: &str) -> Result<String, InvalidServerUrlError> {
+        return Config::validate_and_clean_server_url(url.to_string());
+    }
+
+    #[test]
+    fn server_urls_lose_surrounding_whitespace_and_trailing_slashes() {
+        assert_eq!(clean(" https://miniflux.example.com/ ").unwrap(), "https://miniflux.example.com");
+        assert_eq!(clean("http://localhost:8080/miniflux/").unwrap(), "http://localhost:8080/miniflux");
+        assert_eq!(clean("https://miniflux.example.com").unwrap(), "https://miniflux.example.com");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_urls_need_an_absolute_path() {
+        assert_eq!(clean("unix:///run/miniflux.sock").unwrap(), "unix:///run/miniflux.sock");
+        assert!(clean("unix://run/miniflux.sock").is_err());
+        assert!(clean("unix://").is_err());
+    }
+
+    #[test]
+    fn server_urls_need_an_http_or_https_scheme() {
+        assert!(clean("miniflux.example.com").is_err());
+        assert!(clean("ftp://miniflux.example.com").is_err());
+        assert!(clean("file:///var/miniflux").is_err());
+    }
+
+    #[test]
+    fn server_urls_need_a_hostname() {
+        assert!(clean("https://").is_err());
+        assert!(clean("http://:8080").is_err());
+    }
+
+    #[test]
+    fn server_urls_cant_have_a_query_string_or_fragment() {
+        assert!(clean("https://miniflux.example.com/?token=abc").is_err());
+        assert!(clean("https://miniflux.example.com/#entries").is_err());
+    }
+
+    #[test]
+    fn empty_server_urls_are_rejected() {
+        assert!(clean("").is_err());
+        assert!(clean("   ").is_err());
+    }
+}
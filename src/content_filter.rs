@@ -0,0 +1,118 @@
+use regex::Regex;
+
+use crate::{config::{FilterAction, FilterField, FilterRule, InvalidFilterRuleError}, libminiflux::FeedEntry};
+
+/// The `[[filters]]` rules from the config file, with their regexes compiled, ready to be checked
+/// against entries as they come in from Miniflux
+#[derive(Debug, Clone, Default)]
+pub struct ContentFilter {
+    rules: Vec<(Regex, FilterField, FilterAction)>,
+}
+
+impl ContentFilter {
+    pub fn new(rules: &[FilterRule]) -> Result<ContentFilter, InvalidFilterRuleError> {
+        let compiled = rules.iter()
+            .map(|rule| rule.regex().map(|regex| (regex, rule.field, rule.action)))
+            .collect::<Result<Vec<_>, InvalidFilterRuleError>>()?;
+        return Ok(ContentFilter { rules: compiled });
+    }
+
+    pub fn hides(&self, entry: &FeedEntry) -> bool {
+        return self.any_rule_matches(entry, FilterAction::Hide);
+    }
+
+    pub fn highlights(&self, entry: &FeedEntry) -> bool {
+        return self.any_rule_matches(entry, FilterAction::Highlight);
+    }
+
+    pub fn marks_read(&self, entry: &FeedEntry) -> bool {
+        return self.any_rule_matches(entry, FilterAction::MarkRead);
+    }
+
+    fn any_rule_matches(&self, entry: &FeedEntry, action: FilterAction) -> bool {
+        return self.rules.iter()
+            .filter(|(_, _, rule_action)| *rule_action == action)
+            .any(|(regex, field, _)| {
+                let text = match field {
+                    FilterField::Title => &entry.title,
+                    FilterField::Feed => &entry.feed.title,
+                    FilterField::Url => &entry.url,
+                };
+                regex.is_match(text)
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: [FilterField; 3] = [FilterField::Title, FilterField::Feed, FilterField::Url];
+    const ACTIONS: [FilterAction; 3] = [FilterAction::Hide, FilterAction::Highlight, FilterAction::MarkRead];
+
+    fn rule(pattern: &str, field: FilterField, action: FilterAction) -> FilterRule {
+        return FilterRule { pattern: pattern.to_string(), field, action };
+    }
+
+    // An entry where only the given field mentions "sponsored"
+    fn entry_matching_in(field: FilterField) -> FeedEntry {
+        let (feed_title, title, url) = match field {
+            FilterField::Title => ("Tech News", "Sponsored: buy this", "https://example.com/a"),
+            FilterField::Feed => ("Sponsored Posts", "Buy this", "https://example.com/a"),
+            FilterField::Url => ("Tech News", "Buy this", "https://example.com/sponsored/a"),
+        };
+        return FeedEntry::for_test(1, (10, feed_title), title, url);
+    }
+
+    fn actions_applied(filter: &ContentFilter, entry: &FeedEntry) -> Vec<FilterAction> {
+        return ACTIONS.into_iter()
+            .filter(|action| match action {
+                FilterAction::Hide => filter.hides(entry),
+                FilterAction::Highlight => filter.highlights(entry),
+                FilterAction::MarkRead => filter.marks_read(entry),
+            })
+            .collect();
+    }
+
+    #[test]
+    fn each_field_and_action_only_applies_when_that_field_matches() {
+        for field in FIELDS {
+            for action in ACTIONS {
+                let filter = ContentFilter::new(&[rule("(?i)sponsored", field, action)]).unwrap();
+                for entry_field in FIELDS {
+                    let expected = if entry_field == field { vec![action] } else { vec![] };
+                    assert_eq!(
+                        actions_applied(&filter, &entry_matching_in(entry_field)),
+                        expected,
+                        "rule on {:?} with {:?}, matching text in {:?}", field, action, entry_field
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rules_with_different_actions_apply_independently() {
+        let filter = ContentFilter::new(&[
+            rule("(?i)sponsored", FilterField::Title, FilterAction::Hide),
+            rule("Tech", FilterField::Feed, FilterAction::Highlight),
+        ]).unwrap();
+        let entry = entry_matching_in(FilterField::Title);
+        assert_eq!(actions_applied(&filter, &entry), vec![FilterAction::Hide, FilterAction::Highlight]);
+    }
+
+    #[test]
+    fn no_rules_means_nothing_applies() {
+        let filter = ContentFilter::default();
+        assert_eq!(actions_applied(&filter, &entry_matching_in(FilterField::Title)), vec![]);
+    }
+
+    #[test]
+    fn invalid_regexes_are_rejected() {
+        let error = ContentFilter::new(&[
+            rule("fine", FilterField::Title, FilterAction::Hide),
+            rule("(unclosed", FilterField::Url, FilterAction::MarkRead),
+        ]).unwrap_err();
+        assert!(error.to_string().contains("(unclosed"));
+    }
+}
@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+use crate::{config::{self, Config}, libminiflux::Client};
+
+// The environment variable used to supply the server URL when running `--init` non-interactively
+pub const SERVER_URL_ENV_VAR: &str = "CLIFLUX_SERVER_URL";
+
+fn prompt(question: &str) -> io::Result<String> {
+    print!("{}", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    return Ok(answer.trim().to_string());
+}
+
+fn confirm(question: &str, default: bool) -> io::Result<bool> {
+    let choices = if default { "[Y/n]" } else { "[y/N]" };
+    let answer = prompt(&format!("{} {} ", question, choices))?;
+    return Ok(match answer.to_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default
+    });
+}
+
+fn prompt_server_url() -> io::Result<String> {
+    loop {
+        let answer = prompt("Miniflux server URL (e.g. https://miniflux.example.com): ")?;
+        match Config::validate_and_clean_server_url(answer) {
+            Ok(url) => return Ok(url),
+            Err(e) => eprintln!("{}", e)
+        }
+    }
+}
+
+fn prompt_api_key() -> io::Result<String> {
+    loop {
+        let answer = rpassword::prompt_password("API key (Settings > API Keys in Miniflux; input is hidden): ")?;
+        if !answer.trim().is_empty() {
+            return Ok(answer.trim().to_string());
+        }
+        eprintln!("The API key can't be empty");
+    }
+}
+
+/// Asks the user for their server details, checking them against the server until they work
+/// (or the user gives up).
+pub async fn run() -> Result<Config, Box<dyn std::error::Error>> {
+    println!("Let's set up cliflux. Press Ctrl+C at any time to cancel.\n");
+    let mut config = Config {
+        api_key: None,
+        ..Config::default()
+    };
+    loop {
+        config.server_url = prompt_server_url()?;
+        config.api_key = Some(prompt_api_key()?);
+        if config.server_url.starts_with("https://") {
+            config.allow_invalid_certs = confirm(
+                "Allow invalid TLS certificates (e.g. self-signed ones)?",
+                config.allow_invalid_certs
+            )?;
+            config.use_rustls = confirm(
+                "Use rustls instead of your OS's TLS library (needed for some TLS 1.3-only servers)?",
+                config.use_rustls
+            )?;
+        }
+
+        println!("\nChecking your settings against {}...", config.server_url);
+        let result = match Client::new(&config) {
+            Ok(client) => client.get_me().await.map_err(|e| {
+                let hint = e.hint(&config)
+                    .map(|hint| format!("\n  Hint: {}", hint))
+                    .unwrap_or_default();
+                format!("{}{}", e, hint)
+            }),
+            Err(e) => Err(e.to_string())
+        };
+        match result {
+            Ok(user) => {
+                println!("✔ Logged in as {}\n", user.username);
+                return Ok(config);
+            }
+            Err(message) => {
+                eprintln!("✘ {}\n", message);
+                if !confirm("Try again?", true)? {
+                    return Err(Box::new(io::Error::new(io::ErrorKind::Interrupted, "Setup cancelled")));
+                }
+            }
+        }
+    }
+}
+
+/// Reads the API key from the first line of stdin, for `--api-key-stdin`, so that it doesn't have
+/// to appear on the command line.
+pub fn read_api_key_from_stdin() -> io::Result<Option<String>> {
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    let api_key = line.trim();
+    return Ok(if api_key.is_empty() { None } else { Some(api_key.to_string()) });
+}
+
+/// Builds a config without asking any questions, for provisioning scripts. Anything that isn't
+/// supplied is left as a "FIXME" placeholder to be filled in by hand.
+pub fn non_interactive(
+    server_url: Option<String>,
+    api_key: Option<String>,
+    allow_invalid_certs: bool,
+    use_rustls: bool
+) -> Result<Config, config::InvalidServerUrlError> {
+    let defaults = Config::default();
+    let server_url = match server_url.or_else(|| std::env::var(SERVER_URL_ENV_VAR).ok()) {
+        Some(url) => Config::validate_and_clean_server_url(url)?,
+        None => defaults.server_url.clone()
+    };
+    let api_key = api_key
+        .or_else(|| std::env::var(config::API_KEY_ENV_VAR).ok())
+        .or(defaults.api_key.clone());
+    return Ok(Config {
+        server_url,
+        api_key,
+        allow_invalid_certs,
+        use_rustls,
+        ..defaults
+    });
+}
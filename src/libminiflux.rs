@@ -1,10 +1,129 @@
 extern crate reqwest;
 extern crate serde;
 
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::{cmp::Reverse, error, fmt::Display, path::PathBuf, time::Duration};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use reqwest::{header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER}, Certificate, Identity, StatusCode};
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use url::Url;
+
+use crate::config::{Config, Credentials, UNIX_SOCKET_URL_PREFIX};
+
+#[derive(Debug, Clone)]
+pub struct InvalidCredentialsHeaderError;
+impl Display for InvalidCredentialsHeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Configured credentials contain characters that aren't allowed in an HTTP header")
+    }
+}
+impl error::Error for InvalidCredentialsHeaderError {}
+
+/// The ways that a request to the Miniflux server can fail
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+pub enum Error {
+    Unauthorized,
+    NotFound,
+    ServerError { status: StatusCode, message: String },
+    Timeout,
+    Connection(String),
+    Decode(String),
+}
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Unauthorized => write!(f, "Credentials rejected by the server — check the api_key (or username/password) in config.toml"),
+            Error::NotFound => write!(f, "Not found — it may have been deleted, or server_url in config.toml may be wrong"),
+            Error::ServerError { status, message } => write!(f, "Server returned {}: {}", status, message),
+            Error::Timeout => write!(f, "Timed out waiting for the server to respond"),
+            Error::Connection(details) => write!(f, "Couldn't connect to the server — check server_url in config.toml ({})", details),
+            Error::Decode(details) => write!(f, "Couldn't understand the server's response ({})", details),
+        }
+    }
+}
+impl Error {
+    /// Suggests a likely fix for common misconfigurations, based on what went wrong.
+    pub fn hint(&self, config: &Config) -> Option<String> {
+        match self {
+            Error::Unauthorized if config.username.is_some() => 
+                Some("double-check your username and password".to_string()),
+            Error::Unauthorized => 
+                Some("generate a new API key under Settings > API Keys in Miniflux".to_string()),
+            Error::NotFound => 
+                Some(format!("{} doesn't look like a Miniflux server; server_url should be the base URL, without /v1", config.server_url)),
+            Error::Timeout => 
+                Some(format!(
+                    "the server didn't respond within connect_timeout_secs ({}) / request_timeout_secs ({}); try raising them", 
+                    config.connect_timeout_secs, 
+                    config.request_timeout_secs
+                )),
+            Error::Connection(details) => {
+                let details = details.to_lowercase();
+                if details.contains("certificate") || details.contains("handshake") || details.contains("tls") || details.contains("ssl") {
+                    if config.use_rustls {
+                        Some("TLS failed using rustls; try use_rustls = false, or set ca_cert_path if your server uses a private CA".to_string())
+                    } else {
+                        Some("TLS failed using native-tls; try use_rustls = true (e.g. for TLS 1.3-only servers), or set ca_cert_path if your server uses a private CA".to_string())
+                    }
+                } else {
+                    Some(format!("make sure {} is reachable from this machine", config.server_url))
+                }
+            }
+            _ => None
+        }
+    }
+}
+impl error::Error for Error {}
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            return Error::Timeout;
+        }
+        // The interesting part (e.g. "invalid peer certificate") is often buried a few levels down
+        let mut details = e.to_string();
+        let mut source = error::Error::source(&e);
+        while let Some(cause) = source {
+            let cause_str = cause.to_string();
+            if !details.contains(&cause_str) {
+                details = format!("{}: {}", details, cause_str);
+            }
+            source = cause.source();
+        }
+
+        if e.is_decode() {
+            return Error::Decode(details);
+        }
+        if let Some(status) = e.status() {
+            return Error::ServerError { status, message: details };
+        }
+        return Error::Connection(details);
+    }
+}
 
-use crate::config::Config;
+#[derive(Debug, Clone)]
+pub struct InvalidExtraHeaderError {
+    name: String,
+}
+impl Display for InvalidExtraHeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The \"{}\" entry in [headers] isn't a valid HTTP header name and value", self.name)
+    }
+}
+impl error::Error for InvalidExtraHeaderError {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidTlsFileError {
+    path: String,
+    reason: String,
+}
+impl Display for InvalidTlsFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Couldn't load TLS certificate/key from {}: {}", self.path, self.reason)
+    }
+}
+impl error::Error for InvalidTlsFileError {}
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Feed {
@@ -12,6 +131,14 @@ pub struct Feed {
     pub title: String,
     pub site_url: String,
     pub feed_url: String,
+    #[serde(default)]
+    pub category: Option<Category>,
+    #[serde(default)]
+    pub parsing_error_count: i32,
+    #[serde(default)]
+    pub parsing_error_message: String,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub checked_at: Option<OffsetDateTime>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -24,9 +151,109 @@ pub struct FeedEntry {
     pub feed: Feed,
     pub status: ReadStatus,
     pub starred: bool,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub published_at: Option<OffsetDateTime>,
+    // When it was last marked read/unread (or otherwise changed)
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub changed_at: Option<OffsetDateTime>,
 	#[serde(default)]
 	pub original_content : Option<String>,
 }
+impl FeedEntry {
+    /// The entry's url, minus the parts that differ between copies of the same article: the
+    /// scheme, a leading "www.", trailing slashes, the fragment, and tracking parameters.
+    pub fn canonical_url(&self) -> String {
+        let Ok(url) = Url::parse(self.url.trim()) else {
+            return self.url.trim().to_lowercase();
+        };
+        let host = url.host_str().unwrap_or_default();
+        let query = url.query_pairs()
+            .filter(|(key, _)| !(key.starts_with("utm_") || key == "fbclid" || key == "gclid"))
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("&");
+        let mut canonical = format!(
+            "{}{}",
+            host.strip_prefix("www.").unwrap_or(host),
+            url.path().trim_end_matches('/')
+        );
+        if !query.is_empty() {
+            canonical += &format!("?{}", query);
+        }
+        return canonical;
+    }
+
+    /// The entry's title, lowercased, with punctuation dropped and whitespace squashed, so that
+    /// "Foo: Bar!" and "foo bar" come out the same.
+    pub fn normalized_title(&self) -> String {
+        return self.title.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ");
+    }
+}
+
+#[cfg(test)]
+impl FeedEntry {
+    /// An unread entry with just enough filled in to test things that look at titles, feeds, and urls
+    pub fn for_test(id: i32, feed: (i32, &str), title: &str, url: &str) -> FeedEntry {
+        let (feed_id, feed_title) = feed;
+        return FeedEntry {
+            id,
+            feed_id,
+            title: title.to_string(),
+            url: url.to_string(),
+            content: String::new(),
+            feed: Feed {
+                id: feed_id,
+                title: feed_title.to_string(),
+                site_url: String::new(),
+                feed_url: String::new(),
+                category: None,
+                parsing_error_count: 0,
+                parsing_error_message: String::new(),
+                checked_at: None,
+            },
+            status: ReadStatus::Unread,
+            starred: false,
+            published_at: None,
+            changed_at: None,
+            original_content: None,
+        };
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Category {
+    pub id: i32,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct DiscoveredFeed {
+    pub url: String,
+    pub title: String,
+    #[serde(rename = "type", default)]
+    pub feed_type: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct User {
+    pub id: i32,
+    pub username: String,
+    #[serde(default)]
+    pub is_admin: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct VersionInfo {
+    pub version: String,
+    #[serde(default)]
+    pub commit: String,
+    #[serde(default)]
+    pub build_date: String,
+}
 
 #[derive(Deserialize, Serialize, PartialEq)]
 struct FeedEntriesResponse {
@@ -40,11 +267,193 @@ struct UpdateEntriesRequest {
     pub entry_ids: Vec<i32>,
 }
 
+#[derive(Deserialize, Serialize, PartialEq)]
+struct DiscoverRequest {
+    pub url: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct CreateFeedRequest {
+    pub feed_url: String,
+    pub category_id: i32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct CategoryRequest {
+    pub title: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct CreateFeedResponse {
+    pub feed_id: i32,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Default)]
+pub struct FeedModification {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<i32>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq)]
+struct ErrorResponse {
+    pub error_message: String
+}
+
 #[derive(Deserialize, Serialize, PartialEq)]
 struct OriginalContentResponse {
 	pub content: String
 }
 
+/// What to sort entries by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryOrder {
+    #[default]
+    PublishedAt,
+    Feed,
+    Category,
+    Status,
+    ChangedAt,
+}
+impl EntryOrder {
+    // Sorting by when entries changed is only for the "recently read" view, so it's not in the cycle
+    pub fn cycle(&self) -> EntryOrder {
+        match self {
+            EntryOrder::PublishedAt => EntryOrder::Feed,
+            EntryOrder::Feed => EntryOrder::Category,
+            EntryOrder::Category => EntryOrder::Status,
+            EntryOrder::Status | EntryOrder::ChangedAt => EntryOrder::PublishedAt
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            EntryOrder::PublishedAt => "date",
+            EntryOrder::Feed => "feed",
+            EntryOrder::Category => "category",
+            EntryOrder::Status => "status",
+            EntryOrder::ChangedAt => "last change"
+        }
+    }
+}
+
+impl Display for EntryOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntryOrder::PublishedAt => write!(f, "published_at"),
+            EntryOrder::Feed => write!(f, "feed"),
+            EntryOrder::Category => write!(f, "category"),
+            EntryOrder::Status => write!(f, "status"),
+            EntryOrder::ChangedAt => write!(f, "changed_at"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+impl SortDirection {
+    pub fn toggle(&self) -> SortDirection {
+        match self {
+            SortDirection::Asc => SortDirection::Desc,
+            SortDirection::Desc => SortDirection::Asc
+        }
+    }
+}
+impl Display for SortDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortDirection::Asc => write!(f, "asc"),
+            SortDirection::Desc => write!(f, "desc"),
+        }
+    }
+}
+
+/// How to sort entries, both when fetching them and when merging more into an already-sorted list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct EntrySort {
+    #[serde(default)]
+    pub order: EntryOrder,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+impl EntrySort {
+    // Miniflux can't sort by feed, so for that we fetch the newest entries and group them by feed here
+    fn to_query(self) -> Vec<(&'static str, String)> {
+        let (order, direction) = match self.order {
+            EntryOrder::PublishedAt => ("published_at", self.direction),
+            EntryOrder::Feed => ("published_at", SortDirection::Desc),
+            EntryOrder::Category => ("category_title", self.direction),
+            EntryOrder::Status => ("status", self.direction),
+            EntryOrder::ChangedAt => ("changed_at", self.direction)
+        };
+        return vec![
+            ("order", order.to_string()),
+            ("direction", direction.to_string())
+        ];
+    }
+
+    /// Sorts entries the same way Miniflux would, with the newest first among entries that are
+    /// otherwise tied (like ones from the same feed).
+    pub fn sort(&self, entries: &mut [FeedEntry]) {
+        entries.sort_by_key(|entry| Reverse(entry.published_at));
+        entries.sort_by(|a, b| {
+            let ordering = match self.order {
+                EntryOrder::PublishedAt => a.published_at.cmp(&b.published_at),
+                EntryOrder::Feed => a.feed.title.to_lowercase().cmp(&b.feed.title.to_lowercase()),
+                EntryOrder::Category => {
+                    let category_title = |entry: &FeedEntry| entry.feed.category.as_ref()
+                        .map(|category| category.title.to_lowercase());
+                    category_title(a).cmp(&category_title(b))
+                }
+                EntryOrder::Status => a.status.to_string().cmp(&b.status.to_string()),
+                EntryOrder::ChangedAt => a.changed_at.cmp(&b.changed_at)
+            };
+            match self.direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse()
+            }
+        });
+    }
+}
+
+/// Which entries to fetch from Miniflux
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EntryFilter {
+    pub status: Option<ReadStatus>,
+    pub starred: bool,
+    pub feed_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub search: Option<String>,
+    pub published_after: Option<OffsetDateTime>,
+    pub sort: EntrySort,
+}
+impl EntryFilter {
+    fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![];
+        if let Some(status) = &self.status {
+            query.push(("status", status.to_string()));
+        }
+        if self.starred {
+            query.push(("starred", "true".to_string()));
+        }
+        if let Some(search) = &self.search {
+            query.push(("search", search.clone()));
+        }
+        if let Some(published_after) = self.published_after {
+            query.push(("published_after", published_after.unix_timestamp().to_string()));
+        }
+        query.extend(self.sort.to_query());
+        return query;
+    }
+}
+
 #[derive(PartialEq, Eq, Deserialize, Serialize, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum ReadStatus {
@@ -68,164 +477,594 @@ impl ReadStatus {
     }
 }
 
+// The delay before the first retry of a failed request; each subsequent retry waits twice as long
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+// The longest we'll wait between retries, even if the server asks us (via Retry-After) to wait longer
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
 #[derive(Clone)]
 pub struct Client {
     base_url: String,
     http_client: reqwest::Client,
+    max_retries: u32,
 }
 impl Client {
-    pub fn new(config: &Config) -> Client {
-        let api_key = &config.api_key;
-        let base_url = config.server_url.clone();
+    pub fn new(config: &Config) -> Result<Client, Box<dyn std::error::Error>> {
+        // Requests over a Unix socket still need an http url; the hostname is just ignored
+        let unix_socket_path = config.server_url.strip_prefix(UNIX_SOCKET_URL_PREFIX);
+        let base_url = match unix_socket_path {
+            Some(_) => "http://localhost".to_string(),
+            None => config.server_url.clone()
+        };
         let invalid_certs = config.allow_invalid_certs;
         let use_rustls = config.use_rustls;
 
         let mut default_headers = HeaderMap::new();
-        default_headers.insert(
-            HeaderName::from_bytes(b"X-Auth-Token").unwrap(),
-            HeaderValue::from_str(&api_key).unwrap(),
-        );
+        let (auth_header_name, auth_header_value) = match config.credentials()? {
+            Credentials::ApiKey(api_key) => (HeaderName::from_static("x-auth-token"), api_key),
+            Credentials::Basic { username, password } => (
+                AUTHORIZATION,
+                format!("Basic {}", STANDARD.encode(format!("{}:{}", username, password)))
+            )
+        };
+        let mut auth_header_value = HeaderValue::from_str(&auth_header_value)
+            .map_err(|_| InvalidCredentialsHeaderError)?;
+        auth_header_value.set_sensitive(true);
+        default_headers.insert(auth_header_name, auth_header_value);
+        for (name, value) in &config.headers {
+            let invalid = || InvalidExtraHeaderError { name: name.clone() };
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+            let mut header_value = HeaderValue::from_str(value).map_err(|_| invalid())?;
+            header_value.set_sensitive(true);
+            default_headers.insert(header_name, header_value);
+        }
 
         let mut builder = reqwest::Client::builder();
         if use_rustls {
             builder = builder.use_rustls_tls();
         }
+        builder = Client::configure_certificates(builder, config)?;
+        if config.connect_timeout_secs > 0 {
+            builder = builder.connect_timeout(Duration::from_secs(config.connect_timeout_secs));
+        }
+        if config.request_timeout_secs > 0 {
+            builder = builder.timeout(Duration::from_secs(config.request_timeout_secs));
+        }
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        #[cfg(unix)]
+        if let Some(socket_path) = unix_socket_path {
+            builder = builder.unix_socket(socket_path);
+        }
 
         let http_client = builder
             .danger_accept_invalid_certs(invalid_certs)
             .default_headers(default_headers)
-            .build()
-            .unwrap(); // TODO: better error handling
+            .build()?;
 
-        return Client {
+        return Ok(Client {
             base_url,
             http_client,
+            max_retries: config.max_retries,
+        });
+    }
+
+    /// Adds any custom CA certificates and client certificate (for mTLS) from the config.
+    fn configure_certificates(
+        mut builder: reqwest::ClientBuilder, 
+        config: &Config
+    ) -> Result<reqwest::ClientBuilder, Box<dyn error::Error>> {
+        let read_file = |path: &PathBuf| {
+            std::fs::read(path).map_err(|e| InvalidTlsFileError {
+                path: path.to_string_lossy().to_string(),
+                reason: e.to_string()
+            })
+        };
+        let invalid = |path: &PathBuf, e: reqwest::Error| InvalidTlsFileError {
+            path: path.to_string_lossy().to_string(),
+            reason: e.to_string()
         };
+
+        if let Some(ca_cert_path) = &config.ca_cert_path {
+            let certificates = Certificate::from_pem_bundle(&read_file(ca_cert_path)?)
+                .map_err(|e| invalid(ca_cert_path, e))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        if let Some(client_cert_path) = &config.client_cert_path {
+            let client_cert = read_file(client_cert_path)?;
+            let is_pem = String::from_utf8_lossy(&client_cert).contains("-----BEGIN");
+            // If there's no separate key file, the key has to be in the same PEM file as the certificate
+            let client_key = match &config.client_key_path {
+                Some(client_key_path) => Some(read_file(client_key_path)?),
+                None => None
+            };
+            let identity = 
+                if !is_pem {
+                    if config.use_rustls {
+                        return Err(Box::new(InvalidTlsFileError {
+                            path: client_cert_path.to_string_lossy().to_string(),
+                            reason: "PKCS#12 client certificates aren't supported with use_rustls; convert it to PEM, or set use_rustls = false".to_string()
+                        }));
+                    }
+                    let password = config.client_cert_password.clone().unwrap_or_default();
+                    Identity::from_pkcs12_der(&client_cert, &password)
+                } else if config.use_rustls {
+                    match client_key {
+                        Some(client_key) => Identity::from_pem(&[client_cert, b"\n".to_vec(), client_key].concat()),
+                        None => Identity::from_pem(&client_cert)
+                    }
+                } else {
+                    Identity::from_pkcs8_pem(&client_cert, client_key.as_deref().unwrap_or(&client_cert))
+                }
+                .map_err(|e| invalid(client_cert_path, e))?;
+            builder = builder.identity(identity);
+        }
+        return Ok(builder);
     }
 
-    pub async fn get_unread_entries(
-        &self,
-        limit: i32,
-        offset: i32,
-    ) -> Result<Vec<FeedEntry>, reqwest::Error> {
-        let response: FeedEntriesResponse = self
-            .http_client
-            .get(
-                &format!(
-                    "{}/v1/entries?status=unread&order=published_at&direction=desc&limit={}&offset={}",
-                    self.base_url, limit, offset
-                )
-                .to_string(),
+    // Doubles the delay for each attempt, capped at RETRY_MAX_DELAY (which also keeps a large 
+    // max_retries from overflowing the multiplication)
+    fn backoff_delay(attempt: u32) -> Duration {
+        return 2u32.checked_pow(attempt)
+            .and_then(|factor| RETRY_BASE_DELAY.checked_mul(factor))
+            .map_or(RETRY_MAX_DELAY, |delay| delay.min(RETRY_MAX_DELAY));
+    }
+
+    /// Sends a request, retrying with exponential backoff on timeouts, connection failures, and 
+    /// 429/5xx responses. Only idempotent requests should be sent this way.
+    async fn send_with_retry(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, Error> {
+        let mut attempt = 0;
+        loop {
+            let result = request
+                .try_clone()
+                .expect("Requests sent with retries must not have streaming bodies")
+                .send()
+                .await;
+            let retry_after = match &result {
+                Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS || response.status().is_server_error() => 
+                    Some(
+                        response.headers()
+                            .get(RETRY_AFTER)
+                            .and_then(|value| value.to_str().ok())
+                            .and_then(|value| value.parse::<u64>().ok())
+                            .map(Duration::from_secs)
+                    ),
+                Err(e) if e.is_timeout() || e.is_connect() => Some(None),
+                _ => None
+            };
+            match retry_after {
+                Some(server_requested_delay) if attempt < self.max_retries => {
+                    let delay = server_requested_delay
+                        .unwrap_or_else(|| Client::backoff_delay(attempt))
+                        .min(RETRY_MAX_DELAY);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                _ => return Ok(result?)
+            }
+        }
+    }
+
+    /// Turns non-2xx responses into the appropriate Error, parsing Miniflux's error message out 
+    /// of the body where there is one.
+    async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, Error> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        return match status {
+            StatusCode::UNAUTHORIZED => Err(Error::Unauthorized),
+            StatusCode::NOT_FOUND => Err(Error::NotFound),
+            _ => {
+                let body = response.text().await.unwrap_or_default();
+                let message = match serde_json::from_str::<ErrorResponse>(&body) {
+                    Ok(error_response) => error_response.error_message,
+                    Err(_) => body
+                };
+                Err(Error::ServerError { status, message })
+            }
+        }
+    }
+
+    pub async fn get_me(&self) -> Result<User, Error> {
+        let response = self
+            .send_with_retry(
+                self.http_client.get(format!("{}/v1/me", self.base_url))
             )
-            .send()
-            .await?
-			.error_for_status()?
-            .json::<FeedEntriesResponse>()
             .await?;
+        let user = Client::check_status(response).await?
+            .json::<User>()
+            .await?;
+        return Ok(user);
+    }
 
-        return Ok(response.entries);
+    pub async fn get_version(&self) -> Result<VersionInfo, Error> {
+        let response = self
+            .send_with_retry(
+                self.http_client.get(format!("{}/v1/version", self.base_url))
+            )
+            .await?;
+        let version_info = Client::check_status(response).await?
+            .json::<VersionInfo>()
+            .await?;
+        return Ok(version_info);
     }
 
-    pub async fn get_starred_entries(
+    /// Fetches the entries matching a filter, in the order it asks for
+    pub async fn get_entries(
         &self,
+        filter: &EntryFilter,
         limit: i32,
         offset: i32,
-    ) -> Result<Vec<FeedEntry>, reqwest::Error> {
-        let response: FeedEntriesResponse = self
-            .http_client
-            .get(
-                &format!(
-                    "{}/v1/entries?starred=true&order=published_at&direction=desc&limit={}&offset={}",
-                    self.base_url, limit, offset
-                )
-                .to_string(),
+    ) -> Result<Vec<FeedEntry>, Error> {
+        // Feeds and categories have their own entries endpoints, which take the same parameters
+        let path = match (filter.feed_id, filter.category_id) {
+            (Some(feed_id), _) => format!("/v1/feeds/{}/entries", feed_id),
+            (None, Some(category_id)) => format!("/v1/categories/{}/entries", category_id),
+            (None, None) => "/v1/entries".to_string()
+        };
+        let mut query = filter.to_query();
+        query.push(("limit", limit.to_string()));
+        query.push(("offset", offset.to_string()));
+        let response = self
+            .send_with_retry(
+                self.http_client
+                    .get(format!("{}{}", self.base_url, path))
+                    .query(&query)
             )
-            .send()
-            .await?
-			.error_for_status()?
+            .await?;
+        let mut response: FeedEntriesResponse = Client::check_status(response).await?
             .json::<FeedEntriesResponse>()
             .await?;
 
+        filter.sort.sort(&mut response.entries);
         return Ok(response.entries);
     }
 
+    /// Fetches the entries matching any of several filters (for things Miniflux can't do in one
+    /// request, like entries from a few different feeds), merged into one list sorted like the first
+    /// filter asks for.
+    pub async fn get_entries_matching_any(
+        &self,
+        filters: &[EntryFilter],
+        limit: i32,
+    ) -> Result<Vec<FeedEntry>, Error> {
+        let results = futures::future::try_join_all(
+            filters.iter().map(|filter| self.get_entries(filter, limit, 0))
+        ).await?;
+        let mut entries: Vec<FeedEntry> = vec![];
+        for entry in results.into_iter().flatten() {
+            if !entries.iter().any(|existing| existing.id == entry.id) {
+                entries.push(entry);
+            }
+        }
+        if let Some(filter) = filters.first() {
+            filter.sort.sort(&mut entries);
+        }
+        entries.truncate(limit.max(0) as usize);
+        return Ok(entries);
+    }
+
     pub async fn change_entry_read_status(
         &self,
         entry_id: i32,
         status: ReadStatus,
-    ) -> Result<(), reqwest::Error> {
-        let _ = self
+    ) -> Result<(), Error> {
+        return self.change_entries_read_status(vec![entry_id], status).await;
+    }
+
+    /// Changes the read status of several entries at once, in a single request
+    pub async fn change_entries_read_status(
+        &self,
+        entry_ids: Vec<i32>,
+        status: ReadStatus,
+    ) -> Result<(), Error> {
+        let response = self
             .http_client
             .put(format!("{}/v1/entries", self.base_url))
             .json(&UpdateEntriesRequest {
                 status: status.to_string(),
-                entry_ids: vec![entry_id],
+                entry_ids,
             })
             .send()
-            .await?
-			.error_for_status()?;
+            .await?;
+        Client::check_status(response).await?;
         return Ok(());
     }
 
-    pub async fn toggle_starred(&self, entry_id: i32) -> Result<(), reqwest::Error> {
-        let _ = self
+    pub async fn toggle_starred(&self, entry_id: i32) -> Result<(), Error> {
+        let response = self
             .http_client
             .put(format!(
                 "{}/v1/entries/{}/bookmark",
                 self.base_url, entry_id
             ))
             .send()
-            .await?
-			.error_for_status()?;
+            .await?;
+        Client::check_status(response).await?;
         return Ok(());
     }
 
-    pub(crate) async fn save_entry(&self, entry_id: i32) -> Result<(), reqwest::Error> {
-        let _ = self
+    pub(crate) async fn save_entry(&self, entry_id: i32) -> Result<(), Error> {
+        let response = self
             .http_client
             .post(format!(
                 "{}/v1/entries/{}/save",
                 self.base_url, entry_id
             ))
             .send()
-            .await?
-			.error_for_status()?;
+            .await?;
+        Client::check_status(response).await?;
         return Ok(());
     }
 
-    pub(crate) async fn mark_all_as_read(&self, entry_ids: Vec<i32>) -> Result<(), reqwest::Error> {
-        let _ = self
+    pub(crate) async fn mark_all_as_read(&self, entry_ids: Vec<i32>) -> Result<(), Error> {
+        return self.change_entries_read_status(entry_ids, ReadStatus::Read).await;
+    }
+
+    /// Deletes every read entry (except starred ones) from Miniflux
+    pub async fn flush_history(&self) -> Result<(), Error> {
+        let response = self
             .http_client
-            .put(format!("{}/v1/entries", self.base_url))
-            .json(&UpdateEntriesRequest {
-                status: "read".to_string(),
-                entry_ids,
-            })
+            .put(format!("{}/v1/flush-history", self.base_url))
             .send()
-            .await?
-			.error_for_status()?;
+            .await?;
+        Client::check_status(response).await?;
         return Ok(());
     }
 
-    pub(crate) async fn refresh_all_feeds(&self) -> Result<(), reqwest::Error> {
-        let _ = self
+    pub(crate) async fn refresh_all_feeds(&self) -> Result<(), Error> {
+        let response = self
             .http_client
             .put(format!("{}/v1/feeds/refresh", self.base_url))
             .send()
-            .await?
-            .error_for_status()?;
+            .await?;
+        Client::check_status(response).await?;
         return Ok(());
     }
 
-	pub(crate) async fn fetch_original_content(&self, entry_id: i32) -> Result<String, reqwest::Error> {
-		let response : OriginalContentResponse = self
-			.http_client
-			.get(format!("{}/v1/entries/{}/fetch-content", self.base_url, entry_id))
-			.send()
-			.await?
-			.error_for_status()?
+	pub(crate) async fn fetch_original_content(&self, entry_id: i32) -> Result<String, Error> {
+		let response = self
+			.send_with_retry(
+				self.http_client.get(format!("{}/v1/entries/{}/fetch-content", self.base_url, entry_id))
+			)
+			.await?;
+		let response : OriginalContentResponse = Client::check_status(response).await?
             .json::<OriginalContentResponse>()
 			.await?;
 		return Ok(response.content);
 	}
+
+    pub async fn get_feeds(&self) -> Result<Vec<Feed>, Error> {
+        let response = self
+            .send_with_retry(
+                self.http_client.get(format!("{}/v1/feeds", self.base_url))
+            )
+            .await?;
+        let feeds = Client::check_status(response).await?
+            .json::<Vec<Feed>>()
+            .await?;
+        return Ok(feeds);
+    }
+
+    /// When the newest entry in a feed was published, or None if it doesn't have any entries
+    pub async fn get_latest_entry_date(&self, feed_id: i32) -> Result<Option<OffsetDateTime>, Error> {
+        let response = self
+            .send_with_retry(
+                self.http_client.get(format!(
+                    "{}/v1/feeds/{}/entries?order=published_at&direction=desc&limit=1",
+                    self.base_url, feed_id
+                ))
+            )
+            .await?;
+        let entries_response = Client::check_status(response).await?
+            .json::<FeedEntriesResponse>()
+            .await?;
+        return Ok(entries_response.entries.first().and_then(|entry| entry.published_at));
+    }
+
+    /// Asks Miniflux to check a feed for new entries right away
+    pub async fn refresh_feed(&self, feed_id: i32) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .put(format!("{}/v1/feeds/{}/refresh", self.base_url, feed_id))
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    /// Marks every entry in a feed as read, including ones that haven't been fetched
+    pub async fn mark_feed_as_read(&self, feed_id: i32) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .put(format!("{}/v1/feeds/{}/mark-all-as-read", self.base_url, feed_id))
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    /// Marks every entry in a category as read, including ones that haven't been fetched
+    pub async fn mark_category_as_read(&self, category_id: i32) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .put(format!("{}/v1/categories/{}/mark-all-as-read", self.base_url, category_id))
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    /// Marks every one of a user's entries as read
+    pub async fn mark_user_as_read(&self, user_id: i32) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .put(format!("{}/v1/users/{}/mark-all-as-read", self.base_url, user_id))
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    /// Asks Miniflux to check every feed in a category for new entries right away
+    pub async fn refresh_category(&self, category_id: i32) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .put(format!("{}/v1/categories/{}/refresh", self.base_url, category_id))
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    pub async fn create_category(&self, title: String) -> Result<Category, Error> {
+        let response = self
+            .http_client
+            .post(format!("{}/v1/categories", self.base_url))
+            .json(&CategoryRequest { title })
+            .send()
+            .await?;
+        let category = Client::check_status(response).await?
+            .json::<Category>()
+            .await?;
+        return Ok(category);
+    }
+
+    pub async fn update_category(&self, category_id: i32, title: String) -> Result<Category, Error> {
+        let response = self
+            .http_client
+            .put(format!("{}/v1/categories/{}", self.base_url, category_id))
+            .json(&CategoryRequest { title })
+            .send()
+            .await?;
+        let category = Client::check_status(response).await?
+            .json::<Category>()
+            .await?;
+        return Ok(category);
+    }
+
+    /// Deletes a category. Note that Miniflux deletes the feeds in it along with it.
+    pub async fn delete_category(&self, category_id: i32) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .delete(format!("{}/v1/categories/{}", self.base_url, category_id))
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    pub async fn get_categories(&self) -> Result<Vec<Category>, Error> {
+        let response = self
+            .send_with_retry(
+                self.http_client.get(format!("{}/v1/categories", self.base_url))
+            )
+            .await?;
+        let categories = Client::check_status(response).await?
+            .json::<Vec<Category>>()
+            .await?;
+        return Ok(categories);
+    }
+
+    /// Finds the feeds available at a url, which can be either a feed itself or a website that 
+    /// links to its feeds.
+    pub async fn discover(&self, url: String) -> Result<Vec<DiscoveredFeed>, Error> {
+        let response = self
+            .http_client
+            .post(format!("{}/v1/discover", self.base_url))
+            .json(&DiscoverRequest { url })
+            .send()
+            .await?;
+        let discovered_feeds = Client::check_status(response).await?
+            .json::<Vec<DiscoveredFeed>>()
+            .await?;
+        return Ok(discovered_feeds);
+    }
+
+    /// Subscribes to a feed, returning the new feed's id
+    pub async fn create_feed(&self, feed_url: String, category_id: i32) -> Result<i32, Error> {
+        let response = self
+            .http_client
+            .post(format!("{}/v1/feeds", self.base_url))
+            .json(&CreateFeedRequest { feed_url, category_id })
+            .send()
+            .await?;
+        let create_feed_response = Client::check_status(response).await?
+            .json::<CreateFeedResponse>()
+            .await?;
+        return Ok(create_feed_response.feed_id);
+    }
+
+    pub async fn update_feed(&self, feed_id: i32, modification: FeedModification) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .put(format!("{}/v1/feeds/{}", self.base_url, feed_id))
+            .json(&modification)
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    pub async fn delete_feed(&self, feed_id: i32) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .delete(format!("{}/v1/feeds/{}", self.base_url, feed_id))
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+
+    /// Fetches all of the user's subscriptions as an OPML document
+    pub async fn export_opml(&self) -> Result<String, Error> {
+        let response = self
+            .send_with_retry(
+                self.http_client.get(format!("{}/v1/export", self.base_url))
+            )
+            .await?;
+        let opml = Client::check_status(response).await?
+            .text()
+            .await?;
+        return Ok(opml);
+    }
+
+    /// Subscribes to all of the feeds in an OPML document, creating categories as needed
+    pub async fn import_opml(&self, opml: String) -> Result<(), Error> {
+        let response = self
+            .http_client
+            .post(format!("{}/v1/import", self.base_url))
+            .header(CONTENT_TYPE, "text/xml")
+            .body(opml)
+            .send()
+            .await?;
+        Client::check_status(response).await?;
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_delay_doubles_until_it_reaches_the_maximum() {
+        assert_eq!(Client::backoff_delay(0), RETRY_BASE_DELAY);
+        assert_eq!(Client::backoff_delay(1), RETRY_BASE_DELAY * 2);
+        assert_eq!(Client::backoff_delay(2), RETRY_BASE_DELAY * 4);
+        assert_eq!(Client::backoff_delay(10), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn backoff_delay_doesnt_overflow_for_huge_attempt_counts() {
+        assert_eq!(Client::backoff_delay(31), RETRY_MAX_DELAY);
+        assert_eq!(Client::backoff_delay(32), RETRY_MAX_DELAY);
+        assert_eq!(Client::backoff_delay(u32::MAX), RETRY_MAX_DELAY);
+    }
 }
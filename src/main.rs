@@ -1,39 +1,230 @@
 extern crate serde;
 extern crate toml;
 
-use std::{env, process};
+use std::{env, fs, io::{self, IsTerminal, Read}, process};
 
 use config::Config;
 use libminiflux::Client;
+use opml::Opml;
 
 mod config;
+mod content_filter;
+mod init_wizard;
 mod libminiflux;
+mod opml;
 mod ui;
 
-pub fn init_config_and_exit() {
-    match config::init() {
+pub async fn init_config_and_exit() {
+    // Bail out before asking any questions if there's already a config file
+    if let Err(e) = config::new_config_file_path() {
+        eprintln!("Error writing config file: {}", e);
+        process::exit(1);
+    }
+
+    let interactive = !has_argument("--non-interactive") && io::stdin().is_terminal();
+    let new_config = 
+        if interactive {
+            init_wizard::run().await
+        } else {
+            let api_key = 
+                if has_argument("--api-key-stdin") {
+                    init_wizard::read_api_key_from_stdin()
+                } else {
+                    Ok(None)
+                };
+            api_key
+                .map_err(|e| e.into())
+                .and_then(|api_key| 
+                    init_wizard::non_interactive(
+                        argument_value("--server-url"),
+                        api_key,
+                        has_argument("--allow-invalid-certs"),
+                        has_argument("--use-rustls")
+                    ).map_err(|e| e.into())
+                )
+        };
+    let new_config = match new_config {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    match config::init(&new_config) {
         Ok(config_path) => {
             println!(
-                "Wrote default configuration file to {}",
+                "Wrote configuration file to {}",
                 config_path.to_str().unwrap()
             );
             process::exit(0);
         }
         Err(e) => {
-            eprintln!("Error writing default config file: {}", e);
+            eprintln!("Error writing config file: {}", e);
             process::exit(1);
         }
     }
 }
 
-fn print_config_and_exit() {
+async fn check_config_and_exit() {
 	let config = read_config();
 	println!("{}", config);
-	process::exit(0);
+
+	let miniflux_client = match Client::new(&config) {
+		Ok(client) => client,
+		Err(e) => {
+			eprintln!("✘ Could not set up a client from this configuration: {}", e);
+			process::exit(1);
+		}
+	};
+
+	let mut failures = 0;
+	match miniflux_client.get_me().await {
+		Ok(user) => {
+			println!("✔ Connected to {} as {}", config.server_url, user.username);
+			if config.server_url.starts_with("https://") {
+				println!(
+					"✔ TLS works ({}, certificate verification {})",
+					if config.use_rustls { "rustls" } else { "native-tls" },
+					if config.allow_invalid_certs { "disabled" } else { "enabled" }
+				);
+			}
+		}
+		Err(e) => {
+			failures += 1;
+			eprintln!("✘ Could not fetch /v1/me: {}", e);
+			if let Some(hint) = e.hint(&config) {
+				eprintln!("  Hint: {}", hint);
+			}
+		}
+	}
+
+	match miniflux_client.get_version().await {
+		Ok(version_info) => println!("✔ Miniflux server version {}", version_info.version),
+		Err(e) => {
+			failures += 1;
+			eprintln!("✘ Could not fetch /v1/version: {}", e);
+			if let Some(hint) = e.hint(&config) {
+				eprintln!("  Hint: {}", hint);
+			}
+		}
+	}
+
+	process::exit(if failures == 0 { 0 } else { 1 });
+}
+
+async fn opml_and_exit() {
+    let args: Vec<String> = env::args().skip(2).collect();
+    match args.iter().map(String::as_str).collect::<Vec<&str>>().as_slice() {
+        ["export"] => {
+            let miniflux_client = client_or_exit(&read_config());
+            let exported = match miniflux_client.export_opml().await {
+                Ok(exported) => exported,
+                Err(e) => {
+                    eprintln!("Error exporting feeds: {}", e);
+                    process::exit(1);
+                }
+            };
+            // Re-write it ourselves so that exports are sorted consistently, and diff cleanly
+            match Opml::parse(&exported) {
+                Ok(opml) => print!("{}", opml.to_xml()),
+                Err(_) => print!("{}", exported)
+            }
+        }
+
+        ["import", path] => {
+            let contents = read_opml_file(path);
+            // Check it over before sending it, since Miniflux's errors for bad OPML aren't very helpful
+            let opml = parse_opml_or_exit(path, &contents);
+            let miniflux_client = client_or_exit(&read_config());
+            if let Err(e) = miniflux_client.import_opml(contents).await {
+                eprintln!("Error importing feeds: {}", e);
+                process::exit(1);
+            }
+            println!("Imported {} feeds from {}", opml.feeds.len(), path);
+        }
+
+        ["diff", old_path, new_path] => {
+            let old = parse_opml_or_exit(old_path, &read_opml_file(old_path));
+            let new = parse_opml_or_exit(new_path, &read_opml_file(new_path));
+            let mut differences = 0;
+            for feed in &old.feeds {
+                match new.feeds.iter().find(|f| f.xml_url == feed.xml_url) {
+                    None => {
+                        differences += 1;
+                        println!("- {} <{}>", feed.title, feed.xml_url);
+                    }
+                    Some(f) if f.category != feed.category => {
+                        differences += 1;
+                        println!(
+                            "~ {} <{}> moved from \"{}\" to \"{}\"",
+                            feed.title,
+                            feed.xml_url,
+                            feed.category.as_deref().unwrap_or(""),
+                            f.category.as_deref().unwrap_or("")
+                        );
+                    }
+                    _ => {}
+                }
+            }
+            for feed in new.feeds.iter().filter(|f| !old.feeds.iter().any(|o| o.xml_url == f.xml_url)) {
+                differences += 1;
+                println!("+ {} <{}>", feed.title, feed.xml_url);
+            }
+            process::exit(if differences == 0 { 0 } else { 1 });
+        }
+
+        _ => {
+            eprintln!("USAGE: cliflux opml export | cliflux opml import FILE | cliflux opml diff OLD_FILE NEW_FILE");
+            process::exit(1);
+        }
+    }
+    process::exit(0);
+}
+
+/// Reads an OPML file, where "-" means stdin
+fn read_opml_file(path: &str) -> String {
+    let result = if path == "-" {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents).map(|_| contents)
+    } else {
+        fs::read_to_string(path)
+    };
+    match result {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_opml_or_exit(path: &str, contents: &str) -> Opml {
+    match Opml::parse(contents) {
+        Ok(opml) => opml,
+        Err(e) => {
+            eprintln!("Error in {}: {}", path, e);
+            process::exit(1);
+        }
+    }
 }
 
 pub fn print_help_and_exit() {
     println!("USAGE: cliflux [--init|--help|--check-config]");
+    println!("       cliflux opml [export|import FILE|diff OLD_FILE NEW_FILE]");
+    println!();
+    println!("  --init             Interactively create a config file, checking your settings against the server");
+    println!("    --non-interactive  Don't ask any questions; use these flags (or environment variables) instead:");
+    println!("      --server-url URL     (or ${})", init_wizard::SERVER_URL_ENV_VAR);
+    println!("      --api-key-stdin      Read the API key from the first line of stdin (or set ${})", config::API_KEY_ENV_VAR);
+    println!("      --allow-invalid-certs");
+    println!("      --use-rustls");
+    println!("  --check-config     Print the current config and check that it can connect to the server");
+    println!("  --help             Show this message");
+    println!();
+    println!("  opml export        Print all of your subscriptions as OPML");
+    println!("  opml import FILE   Subscribe to all of the feeds in an OPML file (\"-\" reads from stdin)");
+    println!("  opml diff OLD NEW  Show the feeds added, removed, or moved between two OPML files, without connecting to the server");
     process::exit(0);
 }
 
@@ -41,6 +232,21 @@ fn has_argument(arg: &str) -> bool {
     env::args().into_iter().any(|a| a.to_lowercase() == arg)
 }
 
+/// Finds the value for a flag, given either as `--flag value` or `--flag=value`
+fn argument_value(arg: &str) -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let prefix = format!("{}=", arg);
+    for (idx, a) in args.iter().enumerate() {
+        if a.to_lowercase() == arg {
+            return args.get(idx + 1).cloned();
+        }
+        if a.to_lowercase().starts_with(&prefix) {
+            return Some(a[prefix.len()..].to_string());
+        }
+    }
+    return None;
+}
+
 fn read_config() -> Config {
     let maybe_config_file_path = config::get_config_file_path();
     if maybe_config_file_path.is_err() {
@@ -61,22 +267,35 @@ fn read_config() -> Config {
     maybe_config.unwrap()
 }
 
+fn client_or_exit(config: &Config) -> Client {
+    match Client::new(config) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Error setting up Miniflux client: {}", e);
+            process::exit(1)
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     if has_argument("--help") {
         print_help_and_exit();
     }
     if has_argument("--init") {
-        init_config_and_exit()
+        init_config_and_exit().await
     }
 
 	if has_argument("--check-config") {
-		print_config_and_exit()
+		check_config_and_exit().await
 	}
+    if env::args().nth(1).as_deref() == Some("opml") {
+        opml_and_exit().await
+    }
 
 	let config = read_config();
 
-    let miniflux_client = Client::new(&config);
-    let mut ui = ui::Ui::new(miniflux_client, config.theme);
+    let miniflux_client = client_or_exit(&config);
+    let mut ui = ui::Ui::new(miniflux_client, config);
     ui.run()
 }
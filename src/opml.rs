@@ -0,0 +1,275 @@
+use std::{collections::BTreeMap, error, fmt::Display};
+
+use quick_xml::{escape::escape, events::{BytesStart, Event}, Reader};
+
+#[derive(Debug)]
+pub struct OpmlParseError {
+    reason: String,
+}
+impl Display for OpmlParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Couldn't read OPML: {}", self.reason)
+    }
+}
+impl error::Error for OpmlParseError {}
+impl From<quick_xml::Error> for OpmlParseError {
+    fn from(e: quick_xml::Error) -> Self {
+        OpmlParseError { reason: e.to_string() }
+    }
+}
+
+/// A single feed subscription from an OPML file
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OpmlFeed {
+    pub category: Option<String>,
+    pub title: String,
+    pub xml_url: String,
+    pub html_url: Option<String>,
+}
+
+/// The subscriptions in an OPML file. Only the parts that Miniflux cares about (feeds, and the
+/// categories they're grouped under) are kept.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Opml {
+    pub title: Option<String>,
+    pub feeds: Vec<OpmlFeed>,
+}
+
+impl Opml {
+    pub fn parse(xml: &str) -> Result<Opml, OpmlParseError> {
+        let mut reader = Reader::from_str(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut opml = Opml::default();
+        let mut saw_opml_element = false;
+        let mut saw_body_element = false;
+        let mut in_head_title = false;
+        // For each <outline> we're currently inside of, the category it represents (if it isn't a feed)
+        let mut outline_stack: Vec<Option<String>> = Vec::new();
+
+        loop {
+            match reader.read_event()? {
+                Event::Start(element) if element.name().as_ref() == b"opml" => saw_opml_element = true,
+                Event::Start(element) | Event::Empty(element) if element.name().as_ref() == b"body" => saw_body_element = true,
+                Event::Start(element) if element.name().as_ref() == b"title" => in_head_title = outline_stack.is_empty(),
+                Event::Text(text) if in_head_title => opml.title = Some(text.unescape()?.to_string()),
+                Event::End(element) if element.name().as_ref() == b"title" => in_head_title = false,
+
+                Event::Start(element) if element.name().as_ref() == b"outline" => {
+                    let category = Opml::current_category(&outline_stack);
+                    match Opml::feed_from_outline(&element, category)? {
+                        Some(feed) => {
+                            opml.feeds.push(feed);
+                            outline_stack.push(None);
+                        }
+                        None => outline_stack.push(Some(Opml::outline_text(&element)?.unwrap_or_default())),
+                    }
+                }
+                Event::Empty(element) if element.name().as_ref() == b"outline" => {
+                    let category = Opml::current_category(&outline_stack);
+                    if let Some(feed) = Opml::feed_from_outline(&element, category)? {
+                        opml.feeds.push(feed);
+                    }
+                }
+                Event::End(element) if element.name().as_ref() == b"outline" => {
+                    outline_stack.pop();
+                }
+
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        if !saw_opml_element {
+            return Err(OpmlParseError { reason: "there's no <opml> element; is this an OPML file?".to_string() });
+        }
+        if !saw_body_element {
+            return Err(OpmlParseError { reason: "there's no <body> element, so there are no subscriptions in it".to_string() });
+        }
+        return Ok(opml);
+    }
+
+    // Miniflux only supports one level of categories, so nested categories use the innermost one
+    fn current_category(outline_stack: &[Option<String>]) -> Option<String> {
+        outline_stack.iter().rev().find_map(|category| category.clone())
+    }
+
+    fn outline_text(element: &BytesStart) -> Result<Option<String>, OpmlParseError> {
+        let mut text = None;
+        let mut title = None;
+        for attribute in element.attributes() {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+            match attribute.key.as_ref() {
+                b"text" => text = Some(attribute.unescape_value()?.to_string()),
+                b"title" => title = Some(attribute.unescape_value()?.to_string()),
+                _ => {}
+            }
+        }
+        return Ok(title.or(text));
+    }
+
+    fn feed_from_outline(element: &BytesStart, category: Option<String>) -> Result<Option<OpmlFeed>, OpmlParseError> {
+        let mut xml_url = None;
+        let mut html_url = None;
+        for attribute in element.attributes() {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+            match attribute.key.as_ref() {
+                b"xmlUrl" => xml_url = Some(attribute.unescape_value()?.to_string()),
+                b"htmlUrl" => html_url = Some(attribute.unescape_value()?.to_string()),
+                _ => {}
+            }
+        }
+        return match xml_url {
+            Some(xml_url) => Ok(Some(OpmlFeed {
+                category,
+                title: Opml::outline_text(element)?.unwrap_or_else(|| xml_url.clone()),
+                xml_url,
+                html_url: html_url.filter(|url| !url.is_empty()),
+            })),
+            None => Ok(None)
+        }
+    }
+
+    /// Writes this out as OPML, with categories and feeds sorted by name, so that two exports of the
+    /// same subscriptions come out identical (and can be diffed).
+    pub fn to_xml(&self) -> String {
+        let mut by_category: BTreeMap<Option<String>, Vec<&OpmlFeed>> = BTreeMap::new();
+        for feed in &self.feeds {
+            by_category.entry(feed.category.clone()).or_default().push(feed);
+        }
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<opml version=\"2.0\">\n");
+        xml.push_str("  <head>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", escape(self.title.as_deref().unwrap_or("Miniflux"))));
+        xml.push_str("  </head>\n");
+        xml.push_str("  <body>\n");
+        for (category, mut feeds) in by_category {
+            feeds.sort_by_key(|feed| (feed.title.to_lowercase(), feed.xml_url.clone()));
+            let indent = match &category {
+                Some(category) => {
+                    xml.push_str(&format!("    <outline text=\"{}\">\n", escape(category.as_str())));
+                    "      "
+                }
+                None => "    "
+            };
+            for feed in feeds {
+                xml.push_str(&format!(
+                    "{}<outline title=\"{}\" text=\"{}\" type=\"rss\" xmlUrl=\"{}\"",
+                    indent,
+                    escape(feed.title.as_str()),
+                    escape(feed.title.as_str()),
+                    escape(feed.xml_url.as_str())
+                ));
+                if let Some(html_url) = &feed.html_url {
+                    xml.push_str(&format!(" htmlUrl=\"{}\"", escape(html_url.as_str())));
+                }
+                xml.push_str("/>\n");
+            }
+            if category.is_some() {
+                xml.push_str("    </outline>\n");
+            }
+        }
+        xml.push_str("  </body>\n");
+        xml.push_str("</opml>\n");
+        return xml;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(category: Option<&str>, title: &str, xml_url: &str, html_url: Option<&str>) -> OpmlFeed {
+        return OpmlFeed {
+            category: category.map(str::to_string),
+            title: title.to_string(),
+            xml_url: xml_url.to_string(),
+            html_url: html_url.map(str::to_string),
+        };
+    }
+
+    #[test]
+    fn nested_categories_use_the_innermost_one() {
+        let opml = Opml::parse(r#"
+            <opml version="2.0">
+              <head><title>Subscriptions</title></head>
+              <body>
+                <outline text="Tech">
+                  <outline text="Rust">
+                    <outline text="This Week in Rust" xmlUrl="https://this-week-in-rust.org/rss.xml"/>
+                  </outline>
+                  <outline text="LWN" xmlUrl="https://lwn.net/headlines/rss"/>
+                </outline>
+                <outline text="Uncategorized feed" xmlUrl="https://example.com/feed"/>
+              </body>
+            </opml>
+        "#).unwrap();
+
+        assert_eq!(opml.title, Some("Subscriptions".to_string()));
+        assert_eq!(opml.feeds, vec![
+            feed(Some("Rust"), "This Week in Rust", "https://this-week-in-rust.org/rss.xml", None),
+            feed(Some("Tech"), "LWN", "https://lwn.net/headlines/rss", None),
+            feed(None, "Uncategorized feed", "https://example.com/feed", None),
+        ]);
+    }
+
+    #[test]
+    fn self_closing_and_open_outlines_are_both_feeds() {
+        let opml = Opml::parse(r#"
+            <opml version="2.0">
+              <body>
+                <outline text="News">
+                  <outline text="Self-closing" xmlUrl="https://example.com/a.xml"/>
+                  <outline text="With a closing tag" xmlUrl="https://example.com/b.xml" htmlUrl="https://example.com/b"></outline>
+                  <outline text="After" xmlUrl="https://example.com/c.xml"/>
+                </outline>
+              </body>
+            </opml>
+        "#).unwrap();
+
+        // If the open outline were mistaken for a category, "After" would end up under it
+        assert_eq!(opml.feeds, vec![
+            feed(Some("News"), "Self-closing", "https://example.com/a.xml", None),
+            feed(Some("News"), "With a closing tag", "https://example.com/b.xml", Some("https://example.com/b")),
+            feed(Some("News"), "After", "https://example.com/c.xml", None),
+        ]);
+    }
+
+    #[test]
+    fn titles_and_urls_are_escaped_and_round_trip() {
+        let opml = Opml {
+            title: Some("Tom & Jerry's <feeds>".to_string()),
+            feeds: vec![
+                feed(Some("Q&A \"forums\""), "Ask <HN> & more", "https://example.com/rss?a=1&b=2", Some("https://example.com/?x=\"y\"")),
+                feed(None, "Plain", "https://example.com/plain.xml", None),
+            ],
+        };
+
+        let xml = opml.to_xml();
+        assert!(xml.contains("<title>Tom &amp; Jerry&apos;s &lt;feeds&gt;</title>"));
+        assert!(xml.contains("xmlUrl=\"https://example.com/rss?a=1&amp;b=2\""));
+        assert!(xml.contains("text=\"Q&amp;A &quot;forums&quot;\""));
+
+        let parsed = Opml::parse(&xml).unwrap();
+        assert_eq!(parsed.title, opml.title);
+        let mut expected = opml.feeds.clone();
+        let mut actual = parsed.feeds.clone();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+        assert_eq!(parsed.to_xml(), xml);
+    }
+
+    #[test]
+    fn documents_without_an_opml_element_are_rejected() {
+        assert!(Opml::parse("<rss><channel><title>Not OPML</title></channel></rss>").is_err());
+        assert!(Opml::parse("").is_err());
+    }
+
+    #[test]
+    fn documents_without_a_body_are_rejected() {
+        assert!(Opml::parse(r#"<opml version="2.0"><head><title>Empty</title></head></opml>"#).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use tui_realm_stdlib::List;
+use tuirealm::{command::{Cmd, CmdResult, Direction, Position}, event::{Key, KeyEvent, KeyModifiers}, props::{Alignment, TableBuilder, TextSpan}, AttrValue, Attribute, Component, Event, MockComponent, State, Sub, SubEventClause};
+
+use crate::{libminiflux::Category, ui::{ComponentIds, Message, PromptPurpose, SubClauses, SubscribingComponent}};
+
+/// A list of the user's categories, for creating, renaming, and deleting them.
+pub struct CategoryManager {
+    categories: Vec<Category>,
+    component: List,
+}
+
+impl Default for CategoryManager {
+    fn default() -> Self {
+        Self {
+            categories: Vec::default(),
+            component: List::default()
+                .title(" Manage Categories ", Alignment::Center)
+                .rows(
+                    TableBuilder::default()
+                        .add_row()
+                        .add_col(TextSpan::from("Loading..."))
+                        .build()
+                )
+                .rewind(true)
+                .scroll(true)
+                .highlighted_str(">> ")
+        }
+    }
+}
+
+impl CategoryManager {
+    fn update_categories(&mut self, categories: Vec<Category>) {
+        self.categories = categories;
+        self.categories.sort_by_key(|category| category.title.to_lowercase());
+        let contents = self.categories.iter()
+            .map(|category| vec![TextSpan::from(format!(" {} ", category.title)).bold()])
+            .collect::<Vec<Vec<TextSpan>>>();
+        self.component.attr(
+            Attribute::Content,
+            AttrValue::Table(contents)
+        );
+    }
+
+    fn selected_category(&self) -> Option<&Category> {
+        match self.component.state() {
+            State::One(idx) => self.categories.get(idx.unwrap_usize()),
+            _ => None
+        }
+    }
+}
+
+impl SubscribingComponent for CategoryManager {
+    fn subscriptions(component_id : ComponentIds) -> Vec<Sub<ComponentIds, KeyEvent>> {
+        return vec![
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('q'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::unless_prompt_focused()
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('a'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('n'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('d'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('b'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+        ]
+    }
+}
+
+impl MockComponent for CategoryManager {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            Attribute::Content => {
+                let unwrapped = value.unwrap_payload().unwrap_vec();
+                let updated_categories = unwrapped.iter()
+                    .map(|attr_value| attr_value.clone().unwrap_str())
+                    .map(|json| serde_json::from_str::<Category>(&json).unwrap())
+                    .collect::<Vec<Category>>();
+                self.update_categories(updated_categories)
+            },
+            _ => self.component.attr(attr, value)
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
+
+impl Component<Message, KeyEvent> for CategoryManager {
+    fn on(&mut self, ev: Event<KeyEvent>) -> Option<Message> {
+        let cmd = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Char('q'), .. }) => return Some(Message::AppClose),
+            Event::Keyboard(KeyEvent { code: Key::Char('?'), .. }) => return Some(Message::ShowKeyboardHelp),
+            // Category names show up in the feed list, so it needs reloading on the way back anyway
+            Event::Keyboard(KeyEvent { code: Key::Char('b'), .. }) => return Some(Message::ShowFeedManager),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => return Some(Message::ShowFeedManager),
+            Event::Keyboard(KeyEvent { code: Key::Char('r'), .. }) => return Some(Message::ShowCategoryManager),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('a'), .. }) => return Some(
+                Message::ShowTextPrompt(
+                    PromptPurpose::NewCategory,
+                    "New category name".to_string(),
+                    String::default()
+                )
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('n'), .. }) => return self.selected_category().map(|category|
+                Message::ShowTextPrompt(
+                    PromptPurpose::RenameCategory(category.id),
+                    "Rename category".to_string(),
+                    category.title.clone()
+                )
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('d'), .. }) => return self.selected_category().map(|category|
+                Message::ShowConfirmation(
+                    format!("Delete \"{}\"? This also unsubscribes from every feed in it.", category.title),
+                    Box::new(Message::DeleteCategoryRequested(category.id))
+                )
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('j'), .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Down, .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Char('k'), .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::PageUp, .. }) => Cmd::GoTo(Position::Begin),
+            Event::Keyboard(KeyEvent { code: Key::PageDown, .. }) => Cmd::GoTo(Position::End),
+
+            _ => Cmd::None
+        };
+
+        return match self.perform(cmd) {
+            CmdResult::Changed(_) => Some(Message::Tick),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use tui_realm_stdlib::List;
+use tuirealm::{command::{Cmd, CmdResult, Direction, Position}, event::{Key, KeyEvent, KeyModifiers}, props::{Alignment, BorderType, Borders, TextSpan}, AttrValue, Attribute, Component, Event, MockComponent, State, Sub, SubEventClause};
+
+use crate::ui::{ComponentIds, Message, PromptPurpose, SubClauses, SubscribingComponent};
+
+/// A popup list that asks the user to pick one of several options. Each option is a (label, value)
+/// pair; the value of the chosen option is sent back in a PromptSubmitted message.
+#[derive(Default)]
+pub struct ChoicePrompt {
+    purpose: Option<PromptPurpose>,
+    options: Vec<(String, String)>,
+    component: List,
+}
+
+impl ChoicePrompt {
+    pub fn new(purpose: PromptPurpose, title: &str, options: Vec<(String, String)>) -> Self {
+        let rows = options.iter()
+            .map(|(label, _)| vec![TextSpan::from(label.clone())])
+            .collect::<Vec<Vec<TextSpan>>>();
+        Self {
+            purpose: Some(purpose),
+            options,
+            component: List::default()
+                .title(format!(" {} (Enter to choose, Esc to cancel) ", title), Alignment::Center)
+                .borders(Borders::default().modifiers(BorderType::Rounded))
+                .rows(rows)
+                .rewind(true)
+                .scroll(true)
+                .highlighted_str(">> ")
+        }
+    }
+
+    /// How many lines this prompt needs to show all of its options
+    pub fn height_for(option_count: usize) -> u16 {
+        let border_height = 2;
+        (option_count as u16).saturating_add(border_height)
+    }
+}
+
+impl SubscribingComponent for ChoicePrompt {
+    fn subscriptions(component_id : ComponentIds) -> Vec<Sub<ComponentIds, KeyEvent>> {
+        return vec![
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Esc,
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Enter,
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+        ]
+    }
+}
+
+impl MockComponent for ChoicePrompt {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.component.attr(attr, value)
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Submit => CmdResult::Submit(self.component.state()),
+            _ => self.component.perform(cmd)
+        }
+    }
+}
+
+impl Component<Message, KeyEvent> for ChoicePrompt {
+    fn on(&mut self, ev: Event<KeyEvent>) -> Option<Message> {
+        let cmd = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => return Some(Message::PromptClosed),
+            Event::Keyboard(KeyEvent { code: Key::Char('b'), .. }) => return Some(Message::PromptClosed),
+            Event::Keyboard(KeyEvent { code: Key::Char('j'), .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Down, .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Char('k'), .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::PageUp, .. }) => Cmd::GoTo(Position::Begin),
+            Event::Keyboard(KeyEvent { code: Key::PageDown, .. }) => Cmd::GoTo(Position::End),
+            Event::Keyboard(KeyEvent { code: Key::Enter, .. }) => Cmd::Submit,
+            _ => Cmd::None
+        };
+
+        return match self.perform(cmd) {
+            CmdResult::Submit(State::One(selected_index)) => {
+                let idx = selected_index.unwrap_usize();
+                match (&self.purpose, self.options.get(idx)) {
+                    (Some(purpose), Some((_, value))) => Some(
+                        Message::PromptSubmitted(purpose.clone(), value.clone())
+                    ),
+                    _ => None
+                }
+            }
+            CmdResult::Changed(_) => Some(Message::Tick),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use tuirealm::{command::{Cmd, CmdResult}, event::{Key, KeyEvent, KeyModifiers}, props::Alignment, tui::widgets::{Block, BorderType, Borders, Paragraph, Wrap}, AttrValue, Attribute, Component, Event, MockComponent, Props, State, Sub, SubEventClause};
+
+use crate::ui::{ComponentIds, Message, SubClauses, SubscribingComponent, utils::to_window_title};
+
+/// A popup asking the user to confirm an action before it happens. The message passed in is sent
+/// only if they say yes.
+#[derive(Default)]
+pub struct ConfirmationPrompt {
+    props: Props,
+    question: String,
+    on_confirm: Option<Message>,
+}
+
+impl ConfirmationPrompt {
+    pub fn new(question: &str, on_confirm: Message) -> Self {
+        Self {
+            props: Props::default(),
+            question: question.to_string(),
+            on_confirm: Some(on_confirm)
+        }
+    }
+
+    // Room for the borders, the question, a blank line, and the y/n hint
+    pub const HEIGHT: u16 = 6;
+}
+
+impl SubscribingComponent for ConfirmationPrompt {
+    fn subscriptions(component_id : ComponentIds) -> Vec<Sub<ComponentIds, KeyEvent>> {
+        return vec![
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('y'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('n'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Esc,
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+        ]
+    }
+}
+
+impl MockComponent for ConfirmationPrompt {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
+        let widget = Paragraph::new(format!("{}\n\n(y)es / (n)o", self.question))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .title(to_window_title("Are you sure?"))
+                    .title_alignment(Alignment::Center)
+                    .border_type(BorderType::Rounded)
+                    .borders(Borders::ALL)
+            );
+        frame.render_widget(widget, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _cmd: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Message, KeyEvent> for ConfirmationPrompt {
+    fn on(&mut self, ev: Event<KeyEvent>) -> Option<Message> {
+        return match ev {
+            Event::Keyboard(KeyEvent { code: Key::Char('y'), .. }) => Some(
+                Message::Batch(vec![
+                    Some(Message::PromptClosed),
+                    self.on_confirm.take()
+                ])
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('n'), .. }) => Some(Message::PromptClosed),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => Some(Message::PromptClosed),
+
+            _ => None
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use tuirealm::{Props, Sub, event::{KeyEvent, KeyModifiers, Key}, SubClause, MockComponent, Component, State, command::{Cmd, CmdResult}, Event, tui::widgets::{Block, Borders, Paragraph}, props::Alignment};
+use tuirealm::{Props, Sub, event::{KeyEvent, KeyModifiers, Key}, MockComponent, Component, State, command::{Cmd, CmdResult}, Event, tui::widgets::{Block, Borders, Paragraph}, props::Alignment};
 
 use crate::ui::{SubscribingComponent, ComponentIds, SubClauses, Message};
 
@@ -26,7 +26,7 @@ impl SubscribingComponent for ErrorMessage {
                     code: Key::Char('q'),
                     modifiers: KeyModifiers::NONE
                 }),
-                SubClause::Always
+                SubClauses::unless_prompt_focused()
             ),
 
             Sub::new(
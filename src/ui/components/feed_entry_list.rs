@@ -1,46 +1,216 @@
-use std::vec;
+use std::{collections::{HashMap, HashSet}, str::FromStr, vec};
 
 use tui_realm_stdlib::List;
-use tuirealm::{command::{Cmd, CmdResult, Direction}, event::{KeyEvent, Key, KeyModifiers}, props::{Alignment, TableBuilder, TextSpan}, tui::layout::Rect, AttrValue, Attribute, Component, Event, MockComponent, State, Sub, SubClause, SubEventClause};
-use crate::{config::ThemeConfig, libminiflux::{FeedEntry, ReadStatus}, ui::{ComponentIds, Message, SubscribingComponent, SubClauses, utils::EntryTitle}};
+use tuirealm::{command::{Cmd, CmdResult, Direction}, event::{KeyEvent, Key, KeyModifiers}, props::{Alignment, Color, PropPayload, PropValue, TableBuilder, TextSpan}, tui::layout::Rect, AttrValue, Attribute, Component, Event, MockComponent, State, Sub, SubEventClause};
+use time::OffsetDateTime;
+use crate::{config::{SavedView, ThemeConfig}, content_filter::ContentFilter, libminiflux::{EntryFilter, EntryOrder, EntrySort, Feed, FeedEntry, ReadStatus, SortDirection}, ui::{ComponentIds, EntryChange, EntryScope, Message, PromptPurpose, SubscribingComponent, SubClauses, utils::EntryTitle}};
 
 #[derive(Copy, Debug, PartialEq, Clone)]
 pub enum FeedListViewType {
     UnreadEntries,
     StarredEntries,
+    AllEntries,
+    RecentlyRead,
+    // One of the `[[views]]` from the config file, by its position there
+    SavedView(usize),
 }
 impl FeedListViewType {
-    pub fn title(&self) -> String {
+    pub fn title(&self, saved_views : &[SavedView]) -> String {
         match self {
             FeedListViewType::UnreadEntries => " Unread Entries ".to_string(),
-            FeedListViewType::StarredEntries => " Starred Entries ".to_string()
+            FeedListViewType::StarredEntries => " Starred Entries ".to_string(),
+            FeedListViewType::AllEntries => " All Entries ".to_string(),
+            FeedListViewType::RecentlyRead => " Recently Read ".to_string(),
+            FeedListViewType::SavedView(idx) => match saved_views.get(*idx) {
+                Some(view) => format!(" {} ", view.name),
+                None => " Saved View ".to_string()
+            }
         }
     }
 
-    pub fn cycle(&self) -> FeedListViewType {
+    /// The next view over, going through the built-in views and then any saved ones
+    pub fn cycle(&self, saved_view_count : usize) -> FeedListViewType {
         match self {
             FeedListViewType::UnreadEntries => FeedListViewType::StarredEntries,
-            FeedListViewType::StarredEntries => FeedListViewType::UnreadEntries
+            FeedListViewType::StarredEntries => FeedListViewType::AllEntries,
+            FeedListViewType::AllEntries => FeedListViewType::RecentlyRead,
+            FeedListViewType::RecentlyRead if saved_view_count > 0 => FeedListViewType::SavedView(0),
+            FeedListViewType::SavedView(idx) if idx + 1 < saved_view_count => FeedListViewType::SavedView(idx + 1),
+            FeedListViewType::RecentlyRead | FeedListViewType::SavedView(_) => FeedListViewType::UnreadEntries
+        }
+    }
+
+    fn empty_message(&self) -> &'static str {
+        match self {
+            FeedListViewType::UnreadEntries => "No unread feed items. Press r to refresh.",
+            FeedListViewType::StarredEntries => "No starred feed items. Press r to refresh.",
+            FeedListViewType::AllEntries => "No feed items at all. Press r to refresh.",
+            FeedListViewType::RecentlyRead => "Nothing has been read yet. Press r to refresh.",
+            FeedListViewType::SavedView(_) => "Nothing matches this view. Press r to refresh."
+        }
+    }
+
+    /// How this view is sorted, given how the user would like things sorted. The "recently read"
+    /// view is always most-recently-read first, since that's the point of it, and saved views can
+    /// pick their own sort.
+    pub fn sort(&self, preferred : EntrySort, saved_views : &[SavedView]) -> EntrySort {
+        match self {
+            FeedListViewType::RecentlyRead => EntrySort {
+                order: EntryOrder::ChangedAt,
+                direction: SortDirection::Desc
+            },
+            FeedListViewType::SavedView(idx) => saved_views.get(*idx)
+                .and_then(|view| view.sort)
+                .unwrap_or(preferred),
+            _ => preferred
+        }
+    }
+
+    /// Which entries belong in this view, limited to a feed or category if one is given. Miniflux
+    /// only takes one feed or category per request, so a saved view with several of them needs a
+    /// filter for each.
+    pub fn filters(&self, scope : Option<EntryScope>, saved_views : &[SavedView], now : OffsetDateTime) -> Vec<EntryFilter> {
+        let mut filters = match self {
+            FeedListViewType::UnreadEntries => vec![EntryFilter {
+                status: Some(ReadStatus::Unread),
+                ..EntryFilter::default()
+            }],
+            FeedListViewType::StarredEntries => vec![EntryFilter {
+                starred: true,
+                ..EntryFilter::default()
+            }],
+            FeedListViewType::AllEntries => vec![EntryFilter::default()],
+            FeedListViewType::RecentlyRead => vec![EntryFilter {
+                status: Some(ReadStatus::Read),
+                ..EntryFilter::default()
+            }],
+            FeedListViewType::SavedView(idx) => match saved_views.get(*idx) {
+                Some(view) => Self::saved_view_filters(view, scope.is_some(), now),
+                None => vec![EntryFilter::default()]
+            }
+        };
+        for filter in filters.iter_mut() {
+            match scope {
+                Some(EntryScope::Feed(feed_id)) => filter.feed_id = Some(feed_id),
+                Some(EntryScope::Category(category_id)) => filter.category_id = Some(category_id),
+                Some(EntryScope::All) | None => {}
+            }
+        }
+        return filters;
+    }
+
+    /// Whether entries from this feed belong in this view at all; only saved views limited to some
+    /// feeds or categories leave any out.
+    pub fn includes_feed(&self, feed : &Feed, saved_views : &[SavedView]) -> bool {
+        let FeedListViewType::SavedView(idx) = self else {
+            return true;
+        };
+        return match saved_views.get(*idx) {
+            Some(view) if !view.feed_ids.is_empty() || !view.category_ids.is_empty() =>
+                view.feed_ids.contains(&feed.id)
+                    || feed.category.as_ref().is_some_and(|category| view.category_ids.contains(&category.id)),
+            _ => true
+        }
+    }
+
+    fn saved_view_filters(view : &SavedView, scoped : bool, now : OffsetDateTime) -> Vec<EntryFilter> {
+        let base = EntryFilter {
+            status: view.status.clone(),
+            starred: view.starred,
+            search: view.search.clone(),
+            // Saved views are checked when the config file is loaded, so this can't fail here
+            published_after: view.published_after_time(now).ok().flatten(),
+            ..EntryFilter::default()
+        };
+        // When refreshing a single feed or category, that takes the place of the view's own, and
+        // anything fetched from outside the view gets filtered out afterwards (see includes_feed)
+        if scoped || (view.feed_ids.is_empty() && view.category_ids.is_empty()) {
+            return vec![base];
         }
+        let by_feed = view.feed_ids.iter().map(|feed_id| EntryFilter {
+            feed_id: Some(*feed_id),
+            ..base.clone()
+        });
+        let by_category = view.category_ids.iter().map(|category_id| EntryFilter {
+            category_id: Some(*category_id),
+            ..base.clone()
+        });
+        return by_feed.chain(by_category).collect();
     }
 }
 
+/// How the entry list groups entries under collapsible headers
+#[derive(Copy, Debug, PartialEq, Clone)]
+pub enum EntryGrouping {
+    None,
+    Feed,
+    Category,
+}
+impl EntryGrouping {
+    fn cycle(&self) -> EntryGrouping {
+        match self {
+            EntryGrouping::None => EntryGrouping::Feed,
+            EntryGrouping::Feed => EntryGrouping::Category,
+            EntryGrouping::Category => EntryGrouping::None
+        }
+    }
+
+    // Which group an entry belongs to, as an id and the name to show in the group's header
+    fn group_of(&self, entry : &FeedEntry) -> Option<(i32, String)> {
+        match self {
+            EntryGrouping::None => None,
+            EntryGrouping::Feed => Some((entry.feed_id, entry.feed.title.clone())),
+            EntryGrouping::Category => Some(match &entry.feed.category {
+                Some(category) => (category.id, category.title.clone()),
+                None => (0, "Uncategorized".to_string())
+            })
+        }
+    }
+}
+
+/// One line of the list: either an entry (by its index in `entries`), or the header of a group
+enum ListRow {
+    Header { group_id: i32, title: String, entry_indices: Vec<usize> },
+    Entry(usize),
+}
+
 pub struct FeedEntryList {
     entries: Vec<FeedEntry>,
     component: List,
     view_type : FeedListViewType,
 	theme_config : ThemeConfig,
-	visible_item_count : usize
+	sort : EntrySort,
+	saved_views : Vec<SavedView>,
+	content_filter : ContentFilter,
+	// Whether entries hidden by a filter rule are shown anyway
+	show_hidden : bool,
+	// Whether a feed or category refresh (f/c) is still waiting on Miniflux
+	refreshing : bool,
+	visible_item_count : usize,
+	// Entries that have been individually marked (with space) for a bulk action
+	marked_entry_ids : HashSet<i32>,
+	// Where visual selection mode was started, if it's on; everything between here and the cursor is selected
+	visual_anchor : Option<usize>,
+	grouping : EntryGrouping,
+	collapsed_group_ids : HashSet<i32>,
+	// What's actually shown, since grouping adds header rows and collapsing hides entries
+	rows : Vec<ListRow>,
+	// For entries that other feeds (or the same feed, again) have copies of: the copies, which
+	// aren't shown, keyed by the entry that's shown in their place. All by index in `entries`.
+	duplicates : HashMap<usize, Vec<usize>>,
+	// Entries that an auto-refresh has turned up since the list was last loaded by hand
+	new_entry_ids : HashSet<i32>,
 }
 
 impl FeedEntryList {
-    pub fn new(entries: Vec<FeedEntry>, view_type : FeedListViewType, theme_config : ThemeConfig) -> Self {
+    pub fn new(entries: Vec<FeedEntry>, view_type : FeedListViewType, theme_config : ThemeConfig, sort : EntrySort, saved_views : Vec<SavedView>, content_filter : ContentFilter) -> Self {
         let mut instance =  Self {
             view_type,
             entries: entries.clone(),
 			theme_config,
+			sort,
             component: List::default()
-                .title(view_type.title(), Alignment::Center)
+                .title(view_type.title(&saved_views), Alignment::Center)
                 .rows(
                     TableBuilder::default()
                         .add_row()
@@ -50,34 +220,170 @@ impl FeedEntryList {
                 .rewind(true)
                 .scroll(true)
                 .highlighted_str(">> "),
-			visible_item_count: entries.len()
+			visible_item_count: entries.len(),
+			marked_entry_ids: HashSet::new(),
+			visual_anchor: None,
+			grouping: EntryGrouping::None,
+			collapsed_group_ids: HashSet::new(),
+			rows: vec![],
+			duplicates: HashMap::new(),
+			new_entry_ids: HashSet::new(),
+			saved_views,
+			content_filter,
+			show_hidden: false,
+			refreshing: false
         };
         instance.update_entries(&entries, view_type);
         return instance
     }
 
-    fn spans_for_entry(&self, entry : &FeedEntry) -> Vec<TextSpan> {
-        let title_line = TextSpan::from(EntryTitle::for_entry(entry, &self.theme_config));
-        return vec![
+    fn spans_for_entry(&self, idx : usize, selected : bool) -> Vec<TextSpan> {
+        let entry = &self.entries[idx];
+        let mut title_line = TextSpan::from(EntryTitle::for_entry(entry, &self.theme_config));
+        if self.content_filter.highlights(entry) {
+            title_line = title_line.fg(
+                Color::from_str(&self.theme_config.highlight_color).unwrap_or(Color::Yellow)
+            );
+        }
+        let mut spans = vec![
             title_line,
             TextSpan::from(" »» "),
             TextSpan::from(entry.feed.title.to_string()).italic()
+        ];
+        if let Some(copies) = self.duplicates.get(&idx) {
+            let sources = if copies.len() == 1 { "source" } else { "sources" };
+            spans.push(TextSpan::from(format!(" +{} {}", copies.len(), sources)).italic());
+        }
+        // Only reachable when hidden entries are being shown anyway
+        if self.content_filter.hides(entry) {
+            spans.insert(0, TextSpan::from("(hidden)").italic());
+        }
+        if !selected {
+            return spans;
+        }
+        let selected_color = Color::from_str(&self.theme_config.selected_color)
+            .unwrap_or(Color::DarkGray);
+        return spans.into_iter()
+            .map(|span| span.bg(selected_color))
+            .collect()
+    }
+
+    fn spans_for_group_header(&self, group_id : i32, title : &str, entry_indices : &[usize]) -> Vec<TextSpan> {
+        let unread_count = entry_indices.iter()
+            .filter(|idx| self.entries[**idx].status == ReadStatus::Unread)
+            .count();
+        let marker = if self.collapsed_group_ids.contains(&group_id) { "▸" } else { "▾" };
+        return vec![
+            TextSpan::from(format!("{} {} ", marker, title)).bold(),
+            TextSpan::from(format!("({} unread of {})", unread_count, entry_indices.len())).italic()
         ]
     }
 
+    // Entries count as copies of each other if their urls match once tracking parameters and such
+    // are stripped off, or if their titles match and they're from different feeds (the same feed
+    // can have several posts titled "Weekly roundup"). The first copy, in list order, is the one shown.
+    fn find_duplicates(&self) -> HashMap<usize, Vec<usize>> {
+        let mut duplicates : HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut shown_by_url : HashMap<String, usize> = HashMap::new();
+        let mut shown_by_title : HashMap<String, usize> = HashMap::new();
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if self.is_hidden(idx) {
+                continue;
+            }
+            let url = entry.canonical_url();
+            let title = entry.normalized_title();
+            let shown_idx = shown_by_url.get(&url)
+                .filter(|_| !url.is_empty())
+                .or_else(|| shown_by_title.get(&title).filter(|shown| self.entries[**shown].feed_id != entry.feed_id))
+                .copied();
+            match shown_idx {
+                Some(shown_idx) => duplicates.entry(shown_idx).or_default().push(idx),
+                None => {
+                    shown_by_url.entry(url).or_insert(idx);
+                    if !title.is_empty() {
+                        shown_by_title.entry(title).or_insert(idx);
+                    }
+                }
+            }
+        }
+        return duplicates;
+    }
+
+    // The given entries, plus any copies of them that aren't shown
+    fn with_duplicates(&self, indices : Vec<usize>) -> Vec<usize> {
+        let mut with_duplicates = indices.clone();
+        for idx in indices {
+            if let Some(copies) = self.duplicates.get(&idx) {
+                with_duplicates.extend(copies);
+            }
+        }
+        return with_duplicates;
+    }
+
+    // Groups keep the order of their newest (or first, in whatever the sort order is) entry
+    fn build_rows(&self) -> Vec<ListRow> {
+        let duplicate_indices = self.duplicates.values()
+            .flatten()
+            .copied()
+            .collect::<HashSet<usize>>();
+        if self.grouping == EntryGrouping::None {
+            return (0..self.entries.len())
+                .filter(|idx| !self.is_hidden(*idx) && !duplicate_indices.contains(idx))
+                .map(ListRow::Entry)
+                .collect();
+        }
+        let mut groups : Vec<(i32, String, Vec<usize>)> = vec![];
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if self.is_hidden(idx) || duplicate_indices.contains(&idx) {
+                continue;
+            }
+            let (group_id, title) = self.grouping.group_of(entry).unwrap();
+            match groups.iter_mut().find(|(id, _, _)| *id == group_id) {
+                Some((_, _, entry_indices)) => entry_indices.push(idx),
+                None => groups.push((group_id, title, vec![idx]))
+            }
+        }
+        let mut rows = vec![];
+        for (group_id, title, entry_indices) in groups {
+            let collapsed = self.collapsed_group_ids.contains(&group_id);
+            let entry_rows = entry_indices.clone();
+            rows.push(ListRow::Header { group_id, title, entry_indices });
+            if !collapsed {
+                rows.extend(entry_rows.into_iter().map(ListRow::Entry));
+            }
+        }
+        return rows;
+    }
+
     fn update_entries(&mut self, entries: &Vec<FeedEntry>, view_type : FeedListViewType) {
         self.view_type = view_type;
         self.entries = entries.to_vec();
+        self.visual_anchor = None;
+        self.new_entry_ids.clear();
+        self.marked_entry_ids.retain(|entry_id| entries.iter().any(|entry| entry.id == *entry_id));
         self.redraw();
     }
 
     fn redraw(&mut self) {
+        self.duplicates = self.find_duplicates();
+        self.rows = self.build_rows();
+        let selected_indices = self.selected_indices();
         let contents = 
-            if self.entries.is_empty() {
-                FeedEntryList::zero_state_contents()
+            if self.rows.is_empty() {
+                self.zero_state_contents()
             } else {
-                self.entries.iter()
-                    .map(|entry| self.spans_for_entry(entry))
+                self.rows.iter()
+                    .map(|row| match row {
+                        ListRow::Header { group_id, title, entry_indices } =>
+                            self.spans_for_group_header(*group_id, title, entry_indices),
+                        ListRow::Entry(idx) if self.grouping == EntryGrouping::None =>
+                            self.spans_for_entry(*idx, selected_indices.contains(idx)),
+                        ListRow::Entry(idx) => {
+                            let mut spans = vec![TextSpan::from("  ")];
+                            spans.extend(self.spans_for_entry(*idx, selected_indices.contains(idx)));
+                            spans
+                        }
+                    })
                     .collect::<Vec<Vec<TextSpan>>>()
             };
 
@@ -85,78 +391,493 @@ impl FeedEntryList {
             Attribute::Content, 
             AttrValue::Table(contents)
         );
+        self.redraw_title();
+    }
+
+    fn redraw_title(&mut self) {
+        let mut title = self.view_type.title(&self.saved_views);
+        if self.sort != EntrySort::default() && self.view_type.sort(self.sort, &self.saved_views) == self.sort {
+            title += &format!("— by {} ({}) ", self.sort.order.description(), self.sort.direction);
+        }
+        match self.grouping {
+            EntryGrouping::None => {}
+            EntryGrouping::Feed => title += "— grouped by feed ",
+            EntryGrouping::Category => title += "— grouped by category ",
+        }
+        let hidden_count = self.entries.iter()
+            .filter(|entry| self.content_filter.hides(entry))
+            .count();
+        if hidden_count > 0 {
+            title += &if self.show_hidden {
+                format!("— showing {} hidden ", hidden_count)
+            } else {
+                format!("— {} hidden ", hidden_count)
+            };
+        }
+        let new_entry_count = self.entries.iter()
+            .filter(|entry| self.new_entry_ids.contains(&entry.id))
+            .count();
+        if new_entry_count > 0 {
+            title += &format!("— {} new {} ", new_entry_count, if new_entry_count == 1 { "entry" } else { "entries" });
+        }
+        if self.refreshing {
+            title += "— refreshing… ";
+        }
+        if self.visual_anchor.is_some() {
+            title += "— VISUAL ";
+        }
+        let selected_count = self.selected_indices().len();
+        if selected_count > 0 {
+            title += &format!("— {} selected ", selected_count);
+        }
         self.component.attr(
             Attribute::Title,
-            AttrValue::Title((self.view_type.title(), Alignment::Center))
+            AttrValue::Title((title, Alignment::Center))
+        );
+    }
+
+    /// Swaps out the entries from one feed or category (say, after refreshing just that feed) for
+    /// freshly-fetched ones, leaving the rest of the list and the cursor alone.
+    fn replace_entries_in_scope(&mut self, scope : EntryScope, entries : Vec<FeedEntry>) {
+        let selected_entry_id = self.entry_index_at(self.cursor()).map(|idx| self.entries[idx].id);
+        // Rows are about to move around, so a visual selection wouldn't mean the same thing anymore
+        self.visual_anchor = None;
+        self.entries.retain(|entry| !scope.contains(entry));
+        // The scoped fetch covers the whole feed or category, which can be more than this view shows
+        self.entries.extend(
+            entries.into_iter()
+                .filter(|entry| scope.contains(entry) && self.view_type.includes_feed(&entry.feed, &self.saved_views))
+        );
+        self.view_type.sort(self.sort, &self.saved_views).sort(&mut self.entries);
+        self.redraw();
+        if let Some(idx) = self.entries.iter().position(|entry| Some(entry.id) == selected_entry_id) {
+            if let Some(row) = self.rows.iter().position(|row| matches!(row, ListRow::Entry(i) if *i == idx)) {
+                self.move_cursor_to(row);
+            }
+        }
+    }
+
+    /// Swaps in a freshly-fetched list (from an auto-refresh) without moving the cursor off of the
+    /// highlighted entry or losing the selection. Entries that weren't there before are counted in
+    /// the title, rather than jumped to.
+    fn replace_entries_in_background(&mut self, entries : Vec<FeedEntry>) {
+        let selected_entry_id = self.entry_index_at(self.cursor()).map(|idx| self.entries[idx].id);
+        let anchor_entry_id = self.visual_anchor
+            .and_then(|row| self.entry_for_row(row))
+            .map(|idx| self.entries[idx].id);
+        let known_entry_ids = self.entries.iter()
+            .map(|entry| entry.id)
+            .collect::<HashSet<i32>>();
+        self.new_entry_ids.extend(
+            entries.iter()
+                .map(|entry| entry.id)
+                .filter(|entry_id| !known_entry_ids.contains(entry_id))
+        );
+        self.marked_entry_ids.retain(|entry_id| entries.iter().any(|entry| entry.id == *entry_id));
+        self.entries = entries;
+        self.visual_anchor = None;
+        self.redraw();
+        if let Some(row) = selected_entry_id.and_then(|entry_id| self.row_of_entry(entry_id)) {
+            self.move_cursor_to(row);
+        }
+        if let Some(row) = anchor_entry_id.and_then(|entry_id| self.row_of_entry(entry_id)) {
+            self.visual_anchor = Some(row);
+            self.redraw();
+        }
+    }
+
+    fn row_of_entry(&self, entry_id : i32) -> Option<usize> {
+        let idx = self.entries.iter().position(|entry| entry.id == entry_id)?;
+        return self.rows.iter().position(|row| matches!(row, ListRow::Entry(i) if *i == idx));
+    }
+
+    fn move_cursor_to(&mut self, row : usize) {
+        self.component.attr(
+            Attribute::Value,
+            AttrValue::Payload(PropPayload::One(PropValue::Usize(row)))
         );
     }
 
+    fn entry_index_at(&self, row : usize) -> Option<usize> {
+        match self.rows.get(row)? {
+            ListRow::Entry(idx) => Some(*idx),
+            ListRow::Header { .. } => None
+        }
+    }
+
+    // For actions that need just one entry (like refreshing its feed), a header stands in for the
+    // first entry in its group
+    fn entry_for_row(&self, row : usize) -> Option<usize> {
+        match self.rows.get(row)? {
+            ListRow::Entry(idx) => Some(*idx),
+            ListRow::Header { entry_indices, .. } => entry_indices.first().copied()
+        }
+    }
+
+    // The entries shown on a row: a header only stands for its entries when they're collapsed
+    // (otherwise they have rows of their own)
+    fn entry_indices_in_row(&self, row : usize) -> Vec<usize> {
+        match self.rows.get(row) {
+            Some(ListRow::Entry(idx)) => vec![*idx],
+            Some(ListRow::Header { group_id, entry_indices, .. }) if self.collapsed_group_ids.contains(group_id) =>
+                entry_indices.clone(),
+            _ => vec![]
+        }
+    }
+
+    fn on_group_header(&self) -> bool {
+        return matches!(self.rows.get(self.cursor()), Some(ListRow::Header { .. }));
+    }
+
+    // Collapses (or expands) the group that the highlighted row is in, and moves to its header
+    fn toggle_collapsed(&mut self) -> Option<Message> {
+        let idx = self.entry_for_row(self.cursor())?;
+        let (group_id, _) = self.grouping.group_of(&self.entries[idx])?;
+        if !self.collapsed_group_ids.remove(&group_id) {
+            self.collapsed_group_ids.insert(group_id);
+        }
+        self.visual_anchor = None;
+        self.redraw();
+        if let Some(row) = self.rows.iter().position(|row| matches!(row, ListRow::Header { group_id: id, .. } if *id == group_id)) {
+            self.move_cursor_to(row);
+        }
+        return Some(Message::Tick)
+    }
+
+    fn is_hidden(&self, idx : usize) -> bool {
+        return !self.show_hidden && self.content_filter.hides(&self.entries[idx]);
+    }
+
+    fn toggle_show_hidden(&mut self) -> Option<Message> {
+        let selected_entry_id = self.entry_index_at(self.cursor()).map(|idx| self.entries[idx].id);
+        self.show_hidden = !self.show_hidden;
+        self.visual_anchor = None;
+        // Don't leave anything selected that can't be seen anymore
+        if !self.show_hidden {
+            let content_filter = &self.content_filter;
+            let entries = &self.entries;
+            self.marked_entry_ids.retain(|entry_id| {
+                !entries.iter().any(|entry| entry.id == *entry_id && content_filter.hides(entry))
+            });
+        }
+        self.redraw();
+        if let Some(idx) = self.entries.iter().position(|entry| Some(entry.id) == selected_entry_id) {
+            if let Some(row) = self.rows.iter().position(|row| matches!(row, ListRow::Entry(i) if *i == idx)) {
+                self.move_cursor_to(row);
+            }
+        }
+        return Some(Message::Tick)
+    }
+
+    fn cycle_grouping(&mut self) -> Option<Message> {
+        self.grouping = self.grouping.cycle();
+        self.collapsed_group_ids.clear();
+        self.visual_anchor = None;
+        self.redraw();
+        self.move_cursor_to(0);
+        return Some(Message::Tick)
+    }
+
+    fn cursor(&self) -> usize {
+        return self.component.state()
+            .unwrap_one()
+            .unwrap_usize();
+    }
+
+    // The entries that are either marked, or inside the visual selection
+    fn selected_indices(&self) -> Vec<usize> {
+        let mut selected_indices = self.entries.iter()
+            .enumerate()
+            .filter(|(_, entry)| self.marked_entry_ids.contains(&entry.id))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<usize>>();
+        if let Some(anchor) = self.visual_anchor {
+            let cursor = self.cursor();
+            for row in anchor.min(cursor)..=anchor.max(cursor) {
+                selected_indices.extend(self.entry_indices_in_row(row));
+            }
+        }
+        selected_indices.sort();
+        selected_indices.dedup();
+        return selected_indices;
+    }
+
+    /// Which entries an action should apply to: the selection if there is one, or else just the
+    /// highlighted row (which, for a group header, means everything in the group).
+    fn target_indices(&self) -> Vec<usize> {
+        let selected_indices = self.selected_indices();
+        if !selected_indices.is_empty() {
+            return selected_indices;
+        }
+        return match self.rows.get(self.cursor()) {
+            Some(ListRow::Entry(idx)) => vec![*idx],
+            Some(ListRow::Header { entry_indices, .. }) => entry_indices.clone(),
+            None => vec![]
+        }
+    }
+
+    // Whether an action applies to more than the highlighted entry
+    fn has_selection(&self) -> bool {
+        return self.visual_anchor.is_some() || !self.marked_entry_ids.is_empty() || self.on_group_header();
+    }
+
+    fn clear_selection(&mut self) {
+        self.visual_anchor = None;
+        self.marked_entry_ids.clear();
+    }
+
+    // On a group header, this marks the whole group (or unmarks it, if it's all marked already)
+    fn toggle_mark(&mut self) -> Option<Message> {
+        let entry_ids = match self.rows.get(self.cursor())? {
+            ListRow::Entry(idx) => vec![self.entries[*idx].id],
+            ListRow::Header { entry_indices, .. } => entry_indices.iter()
+                .map(|idx| self.entries[*idx].id)
+                .collect()
+        };
+        if entry_ids.iter().all(|entry_id| self.marked_entry_ids.contains(entry_id)) {
+            for entry_id in entry_ids {
+                self.marked_entry_ids.remove(&entry_id);
+            }
+        } else {
+            self.marked_entry_ids.extend(entry_ids);
+        }
+        self.component.perform(Cmd::Move(Direction::Down));
+        self.redraw();
+        return Some(Message::Tick)
+    }
+
+    // Turning visual mode off keeps what was selected, as marks, so that several ranges can be
+    // combined into one selection.
+    fn toggle_visual_mode(&mut self) -> Option<Message> {
+        if self.rows.is_empty() {
+            return None
+        }
+        match self.visual_anchor {
+            Some(_) => {
+                for idx in self.selected_indices() {
+                    self.marked_entry_ids.insert(self.entries[idx].id);
+                }
+                self.visual_anchor = None;
+            },
+            None => self.visual_anchor = Some(self.cursor())
+        }
+        self.redraw();
+        return Some(Message::Tick)
+    }
+
+    // Read entries in the selection become unread if they're all read already, and read otherwise
+    fn toggle_read_status_of_selection(&mut self) -> Option<Message> {
+        let indices = self.with_duplicates(self.target_indices());
+        let new_status =
+            if indices.iter().any(|idx| self.entries[*idx].status == ReadStatus::Unread) {
+                ReadStatus::Read
+            } else {
+                ReadStatus::Unread
+            };
+        let mut entry_ids = vec![];
+        for idx in indices {
+            let entry = &mut self.entries[idx];
+            if entry.status != new_status {
+                entry.status = new_status.clone();
+                entry_ids.push(entry.id);
+            }
+        }
+        self.clear_selection();
+        self.redraw();
+        if entry_ids.is_empty() {
+            return None
+        }
+        return Some(Message::ChangeEntriesReadStatus(entry_ids, new_status))
+    }
+
+    // Same idea as read status: star them all, unless they're all starred already. Miniflux only
+    // toggles stars one entry at a time, so this is one request per entry that changes.
+    fn toggle_starred_for_selection(&mut self) -> Option<Message> {
+        let indices = self.with_duplicates(self.target_indices());
+        let starred = indices.iter().any(|idx| !self.entries[*idx].starred);
+        let mut messages = vec![];
+        for idx in indices {
+            let entry = &mut self.entries[idx];
+            if entry.starred != starred {
+                entry.starred = starred;
+                messages.push(Some(Message::ToggleStarred(entry.id)));
+            }
+        }
+        self.clear_selection();
+        self.redraw();
+        return Some(Message::Batch(messages))
+    }
+
+    fn save_selection(&mut self) -> Option<Message> {
+        let messages = self.with_duplicates(self.target_indices()).into_iter()
+            .map(|idx| Some(Message::SaveEntry(self.entries[idx].id)))
+            .collect();
+        self.clear_selection();
+        self.redraw();
+        return Some(Message::Batch(messages))
+    }
+
+    // Unlike the other bulk actions, a group header doesn't stand for its whole group here, so that
+    // one keypress can't open dozens of browser tabs; only an explicit selection opens several
+    fn open_selection_in_browser(&mut self) -> Option<Message> {
+        let mut indices = self.selected_indices();
+        if indices.is_empty() {
+            indices = self.entry_index_at(self.cursor()).into_iter().collect();
+        }
+        for idx in indices {
+            let _ = open::that(&self.entries[idx].url);
+        }
+        self.clear_selection();
+        self.redraw();
+        return Some(Message::Tick)
+    }
+
+    fn refresh_scope(&self, idx: usize, scope_of : fn(&FeedEntry) -> Option<EntryScope>) -> Option<Message> {
+        let entry = self.entries.get(idx)?;
+        return scope_of(entry).map(|scope| Message::RefreshScopeRequested(scope, self.view_type));
+    }
+
+    // Asks whether to mark the highlighted entry's feed, its category, or everything as read
+    fn choose_scope_to_mark_as_read(&self, idx: usize) -> Option<Message> {
+        let entry = self.entries.get(idx)?;
+        let mut options = vec![
+            (format!("Everything in \"{}\"", entry.feed.title), EntryScope::feed_of(entry))
+        ];
+        if let (Some(category), Some(scope)) = (&entry.feed.category, EntryScope::category_of(entry)) {
+            options.push((format!("Everything in the \"{}\" category", category.title), scope));
+        }
+        options.push(("Everything in every feed".to_string(), EntryScope::All));
+        return Some(Message::ShowChoicePrompt(
+            PromptPurpose::ChooseScopeToMarkAsRead,
+            "Mark what as read?".to_string(),
+            options.into_iter()
+                .map(|(label, scope)| (label, serde_json::to_string(&scope).unwrap()))
+                .collect()
+        ));
+    }
+
+    // Copies of the entry (see find_duplicates) go along with it
     fn toggle_read_status(&mut self, idx: usize) -> Option<Message> {
         if idx < self.entries.len() {
-            {
+            let new_status = self.entries[idx].status.toggle();
+            let mut entry_ids = vec![];
+            for idx in self.with_duplicates(vec![idx]) {
                 let entry = &mut self.entries[idx];
-                entry.status = entry.status.toggle();
+                if entry.status != new_status {
+                    entry.status = new_status.clone();
+                    entry_ids.push(entry.id);
+                }
             }
             self.redraw();
-            let entry = &self.entries[idx];
-            return Some(Message::ChangeEntryReadStatus(entry.id, entry.status.clone()))
+            return Some(Message::ChangeEntriesReadStatus(entry_ids, new_status))
         }
         return None
     }
 
+    // Copies of the entry (see find_duplicates) are starred or unstarred along with it
     fn toggle_starred(&mut self, idx: usize) -> Option<Message> {
         if idx < self.entries.len() {
-            {
+            let starred = !self.entries[idx].starred;
+            let mut messages = vec![];
+            for idx in self.with_duplicates(vec![idx]) {
                 let entry = &mut self.entries[idx];
-                entry.starred = !entry.starred;
+                if entry.starred != starred {
+                    entry.starred = starred;
+                    messages.push(Some(Message::ToggleStarred(entry.id)));
+                }
             }
             self.redraw();
-            let entry = &self.entries[idx];
-            return Some(Message::ToggleStarred(entry.id))
+            return Some(Message::Batch(messages))
         }
         return None
     }
 
 	fn save_entry(&mut self, idx: usize) -> Option<Message> {
 		if idx < self.entries.len() {
-			let entry = &self.entries[idx];
-			return Some(Message::SaveEntry(entry.id));
+			let messages = self.with_duplicates(vec![idx]).into_iter()
+				.map(|idx| Some(Message::SaveEntry(self.entries[idx].id)))
+				.collect();
+			return Some(Message::Batch(messages));
 		}
 		return None
 	}
 
+    // Copies of the entry (see find_duplicates) are marked as read too, since it's the same article
     fn mark_as_read(&mut self, idx: usize) -> Option<Message> {
         if idx < self.entries.len() {
-            {
+            let mut entry_ids = vec![];
+            for idx in self.with_duplicates(vec![idx]) {
                 let entry = &mut self.entries[idx];
-                if entry.status == ReadStatus::Read {
-                    return None;
+                if entry.status == ReadStatus::Unread {
+                    entry.status = ReadStatus::Read;
+                    entry_ids.push(entry.id);
                 }
-                entry.status = ReadStatus::Read;
+            }
+            if entry_ids.is_empty() {
+                return None;
             }
             self.redraw();
-            let entry = &self.entries[idx];
-            return Some(Message::ChangeEntryReadStatus(entry.id, entry.status.clone()))
+            return Some(Message::ChangeEntriesReadStatus(entry_ids, ReadStatus::Read))
         }
         return None
     }
 
-	fn mark_all_as_read(&mut self) -> Option<Message> {
-		if self.entries.is_empty() {
+	// Marks the unread entries in the given range of rows as read, in one batch
+	fn mark_rows_as_read(&mut self, rows: std::ops::Range<usize>) -> Option<Message> {
+		let indices = self.with_duplicates(
+			rows.flat_map(|row| self.entry_indices_in_row(row)).collect()
+		);
+		let mut entry_ids = vec![];
+		for idx in indices {
+			let entry = &mut self.entries[idx];
+			if entry.status == ReadStatus::Unread {
+				entry.status = ReadStatus::Read;
+				entry_ids.push(entry.id);
+			}
+		}
+		if entry_ids.is_empty() {
 			return None
 		}
-		let mut entry_ids = vec![];
-		for entry in &mut self.entries {
-			entry.status = ReadStatus::Read;
-			entry_ids.push(entry.id);
+		self.redraw();
+		return Some(Message::ChangeEntriesReadStatus(entry_ids, ReadStatus::Read))
+	}
+
+	// Applies changes made elsewhere (say, by undoing) to the entries that are already loaded
+	fn apply_changes(&mut self, changes : Vec<EntryChange>) {
+		for change in &changes {
+			for entry in &mut self.entries {
+				change.apply_to(entry);
+			}
 		}
 		self.redraw();
-		return Some(Message::MarkAllAsRead(entry_ids))
 	}
 
-    fn zero_state_contents() -> Vec<Vec<TextSpan>> {
+	// Only asks; the Model marks them (here and on the server) once that's confirmed
+	fn mark_all_as_read(&mut self) -> Option<Message> {
+		// Only the ones that weren't read already, so that undoing this doesn't mark those as unread,
+		// and not ones hidden by filter rules, since "all" should mean what's on screen
+		let entry_ids = self.entries.iter()
+			.enumerate()
+			.filter(|(idx, entry)| entry.status == ReadStatus::Unread && !self.is_hidden(*idx))
+			.map(|(_, entry)| entry.id)
+			.collect::<Vec<i32>>();
+		if entry_ids.is_empty() {
+			return None
+		}
+		return Some(Message::ShowConfirmation(
+			format!("Mark all {} entries in this list as read?", entry_ids.len()),
+			Box::new(Message::MarkAllAsRead(entry_ids))
+		))
+	}
+
+    fn zero_state_contents(&self) -> Vec<Vec<TextSpan>> {
+        if !self.entries.is_empty() {
+            return vec![
+                vec![TextSpan::from("Everything here is hidden by your filter rules. Press h to show it anyway.")]
+            ];
+        }
         vec![
-            vec![TextSpan::from("No unread feed items. Press r to refresh.")]
+            vec![TextSpan::from(self.view_type.empty_message())]
         ]
     }
 
@@ -178,7 +899,7 @@ impl SubscribingComponent for FeedEntryList {
                     code: Key::Char('q'),
                     modifiers: KeyModifiers::NONE
                 }), 
-                SubClause::Always
+                SubClauses::unless_prompt_focused()
             ),
 
             Sub::new(
@@ -259,6 +980,126 @@ impl SubscribingComponent for FeedEntryList {
                 SubClauses::when_focused(&component_id)
             ),
 
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('f'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('c'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('g'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('z'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('h'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('S'),
+                    modifiers: KeyModifiers::SHIFT
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('D'),
+                    modifiers: KeyModifiers::SHIFT
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('X'),
+                    modifiers: KeyModifiers::SHIFT
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('K'),
+                    modifiers: KeyModifiers::SHIFT
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('J'),
+                    modifiers: KeyModifiers::SHIFT
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('u'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char(' '),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('V'),
+                    modifiers: KeyModifiers::SHIFT
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Esc,
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('o'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+
             Sub::new(
                 SubEventClause::Keyboard(KeyEvent {
                     code: Key::Char('v'),
@@ -304,6 +1145,24 @@ impl MockComponent for FeedEntryList {
                     .collect::<Vec<FeedEntry>>();
                 self.update_entries(&updated_entries, self.view_type)
             },
+            Attribute::Custom("entry_changes") => {
+                let changes = serde_json::from_str::<Vec<EntryChange>>(&value.unwrap_payload().unwrap_one().unwrap_str()).unwrap();
+                self.apply_changes(changes)
+            },
+            Attribute::Custom("background_entries") => {
+                let entries = serde_json::from_str::<Vec<FeedEntry>>(&value.unwrap_payload().unwrap_one().unwrap_str()).unwrap();
+                self.replace_entries_in_background(entries)
+            },
+            Attribute::Custom("refreshing") => {
+                self.refreshing = value.unwrap_flag();
+                self.redraw_title()
+            },
+            Attribute::Custom("scoped_entries") => {
+                let (scope, entries) = value.unwrap_payload().unwrap_tup2();
+                let scope = serde_json::from_str::<EntryScope>(&scope.unwrap_str()).unwrap();
+                let entries = serde_json::from_str::<Vec<FeedEntry>>(&entries.unwrap_str()).unwrap();
+                self.replace_entries_in_scope(scope, entries)
+            },
             _ => self.component.attr(attr, value)
         }
     }
@@ -321,8 +1180,13 @@ impl MockComponent for FeedEntryList {
             Cmd::Custom("refresh") => CmdResult::Custom("refresh"),
             Cmd::Custom("force_refresh") => CmdResult::Custom("force_refresh"),
 
+            Cmd::Custom("manage_feeds") => CmdResult::Custom("manage_feeds"),
+
+            Cmd::Custom("refresh_feed") => CmdResult::Custom("refresh_feed"),
+            Cmd::Custom("refresh_category") => CmdResult::Custom("refresh_category"),
+
             Cmd::Custom("change_view") => {
-                self.view_type = self.view_type.cycle();
+                self.view_type = self.view_type.cycle(self.saved_views.len());
                 CmdResult::Custom("refresh")
             }
 
@@ -333,6 +1197,26 @@ impl MockComponent for FeedEntryList {
             Cmd::Custom("save_entry") => CmdResult::Custom("save_entry"),
 
 			Cmd::Custom("mark_all_as_read") => CmdResult::Custom("mark_all_as_read"),
+			Cmd::Custom("mark_scope_as_read") => CmdResult::Custom("mark_scope_as_read"),
+			Cmd::Custom("flush_history") => CmdResult::Custom("flush_history"),
+			Cmd::Custom("cycle_grouping") => CmdResult::Custom("cycle_grouping"),
+			Cmd::Custom("toggle_collapsed") => CmdResult::Custom("toggle_collapsed"),
+			Cmd::Custom("toggle_show_hidden") => CmdResult::Custom("toggle_show_hidden"),
+			Cmd::Custom("cycle_sort_order") => {
+				self.sort.order = self.sort.order.cycle();
+				CmdResult::Custom("sort_changed")
+			}
+			Cmd::Custom("toggle_sort_direction") => {
+				self.sort.direction = self.sort.direction.toggle();
+				CmdResult::Custom("sort_changed")
+			}
+			Cmd::Custom("mark_above_as_read") => CmdResult::Custom("mark_above_as_read"),
+			Cmd::Custom("mark_below_as_read") => CmdResult::Custom("mark_below_as_read"),
+			Cmd::Custom("undo") => CmdResult::Custom("undo"),
+			Cmd::Custom("toggle_mark") => CmdResult::Custom("toggle_mark"),
+			Cmd::Custom("toggle_visual_mode") => CmdResult::Custom("toggle_visual_mode"),
+			Cmd::Custom("clear_selection") => CmdResult::Custom("clear_selection"),
+			Cmd::Custom("open_in_browser") => CmdResult::Custom("open_in_browser"),
 
             Cmd::Submit => CmdResult::Submit(self.component.state()),
 
@@ -392,6 +1276,76 @@ impl Component<Message, KeyEvent> for FeedEntryList {
 				..
             }) => Cmd::Custom("mark_all_as_read"),
 
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('A'),
+				..
+            }) => Cmd::Custom("mark_scope_as_read"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('X'),
+				..
+            }) => Cmd::Custom("flush_history"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('S'),
+				..
+            }) => Cmd::Custom("cycle_sort_order"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('g'),
+				..
+            }) => Cmd::Custom("cycle_grouping"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('z'),
+				..
+            }) => Cmd::Custom("toggle_collapsed"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('h'),
+				..
+            }) => Cmd::Custom("toggle_show_hidden"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('D'),
+				..
+            }) => Cmd::Custom("toggle_sort_direction"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('K'),
+				..
+            }) => Cmd::Custom("mark_above_as_read"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('J'),
+				..
+            }) => Cmd::Custom("mark_below_as_read"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('u'),
+				..
+            }) => Cmd::Custom("undo"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char(' '),
+				..
+            }) => Cmd::Custom("toggle_mark"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('V'),
+				..
+            }) => Cmd::Custom("toggle_visual_mode"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Esc,
+				..
+            }) => Cmd::Custom("clear_selection"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('o'),
+				..
+            }) => Cmd::Custom("open_in_browser"),
+
             Event::Keyboard(KeyEvent {
                 code: Key::Char('s'),
 				..
@@ -419,6 +1373,21 @@ impl Component<Message, KeyEvent> for FeedEntryList {
                 Cmd::Custom("force_refresh")
             },
 
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('F'),
+                modifiers: KeyModifiers::SHIFT
+            }) => Cmd::Custom("manage_feeds"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('f'),
+                ..
+            }) => Cmd::Custom("refresh_feed"),
+
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('c'),
+                ..
+            }) => Cmd::Custom("refresh_category"),
+
             Event::Keyboard(KeyEvent {
                 code: Key::Char('v'),
                 ..
@@ -433,15 +1402,16 @@ impl Component<Message, KeyEvent> for FeedEntryList {
         };
 
         return match self.perform(cmd) {
+            CmdResult::Submit(_) if self.on_group_header() => self.toggle_collapsed(),
             CmdResult::Submit(State::One(selected_index)) => {
-                let idx = selected_index.unwrap_usize();
+                let idx = self.entry_index_at(selected_index.unwrap_usize())?;
                 if idx < self.entries.len() {
                     let change_state_message = self.mark_as_read(idx);
                     let entry = &self.entries[idx];
                     return Some(
                         Message::Batch(vec![
                             change_state_message,
-                            Some(Message::EntrySelected(entry.clone()))
+                            Some(Message::EntrySelected(Box::new(entry.clone())))
                         ])
                     );
                 }
@@ -453,35 +1423,161 @@ impl Component<Message, KeyEvent> for FeedEntryList {
 
             CmdResult::Custom("refresh") => Some(Message::RefreshRequested(self.view_type)),
             CmdResult::Custom("force_refresh") => Some(Message::ForceRefreshRequested(self.view_type)),
+            CmdResult::Custom("manage_feeds") => Some(Message::ShowFeedManager),
+
+            CmdResult::Custom("refresh_feed") => {
+                let idx = self.entry_for_row(self.cursor())?;
+                self.refresh_scope(idx, |entry| Some(EntryScope::feed_of(entry)))
+            }
 
+            CmdResult::Custom("refresh_category") => {
+                let idx = self.entry_for_row(self.cursor())?;
+                self.refresh_scope(idx, EntryScope::category_of)
+            }
+
+            CmdResult::Custom("toggle_read_status") if self.has_selection() => self.toggle_read_status_of_selection(),
             CmdResult::Custom("toggle_read_status") => {
-                let idx = self.component.state()
-                    .unwrap_one()
-                    .unwrap_usize();
+                let idx = self.entry_index_at(self.cursor())?;
                 self.toggle_read_status(idx)
             }
 
+            CmdResult::Custom("toggle_starred") if self.has_selection() => self.toggle_starred_for_selection(),
             CmdResult::Custom("toggle_starred") => {
-                let idx = self.component.state()
-                    .unwrap_one()
-                    .unwrap_usize();
+                let idx = self.entry_index_at(self.cursor())?;
                 self.toggle_starred(idx)
             }
 
+            CmdResult::Custom("save_entry") if self.has_selection() => self.save_selection(),
             CmdResult::Custom("save_entry") => {
-                let idx = self.component.state()
-                    .unwrap_one()
-                    .unwrap_usize();
+                let idx = self.entry_index_at(self.cursor())?;
                 self.save_entry(idx)
             }
 
+            CmdResult::Custom("open_in_browser") => self.open_selection_in_browser(),
+
+            CmdResult::Custom("toggle_mark") => self.toggle_mark(),
+            CmdResult::Custom("toggle_visual_mode") => self.toggle_visual_mode(),
+            CmdResult::Custom("clear_selection") => {
+                self.clear_selection();
+                self.redraw();
+                Some(Message::Tick)
+            }
+
 			CmdResult::Custom("mark_all_as_read") => {
 				self.mark_all_as_read()
 			}
 
-            CmdResult::Changed(_) => Some(Message::Tick),
+			CmdResult::Custom("mark_above_as_read") => {
+				let row = self.cursor();
+				self.mark_rows_as_read(0..row.min(self.rows.len()))
+			}
+
+			CmdResult::Custom("mark_below_as_read") => {
+				let row = self.cursor();
+				self.mark_rows_as_read((row + 1).min(self.rows.len())..self.rows.len())
+			}
+
+			CmdResult::Custom("undo") => Some(Message::Undo),
+
+			CmdResult::Custom("sort_changed") => Some(Message::SortChanged(self.sort)),
+			CmdResult::Custom("cycle_grouping") => self.cycle_grouping(),
+			CmdResult::Custom("toggle_collapsed") => self.toggle_collapsed(),
+			CmdResult::Custom("toggle_show_hidden") => self.toggle_show_hidden(),
+
+			CmdResult::Custom("flush_history") => Some(Message::ShowConfirmation(
+				"Delete every read entry (except starred ones) from Miniflux?".to_string(),
+				Box::new(Message::FlushHistory)
+			)),
+
+			CmdResult::Custom("mark_scope_as_read") => {
+				let idx = self.entry_for_row(self.cursor())?;
+				self.choose_scope_to_mark_as_read(idx)
+			}
+
+            CmdResult::Changed(_) => {
+                // The visual selection follows the cursor
+                if self.visual_anchor.is_some() {
+                    self.redraw();
+                }
+                Some(Message::Tick)
+            }
 
             _ => None
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::libminiflux::Category;
+    use super::*;
+
+    fn list_of(entries: Vec<FeedEntry>) -> FeedEntryList {
+        return FeedEntryList::new(
+            entries,
+            FeedListViewType::UnreadEntries,
+            ThemeConfig::default(),
+            EntrySort::default(),
+            vec![],
+            ContentFilter::default()
+        );
+    }
+
+    #[test]
+    fn entries_with_the_same_canonical_url_are_duplicates() {
+        let list = list_of(vec![
+            FeedEntry::for_test(1, (10, "Alpha"), "Some article", "https://www.example.com/post/?utm_source=alpha"),
+            FeedEntry::for_test(2, (11, "Beta"), "A different title", "http://example.com/post#comments"),
+            FeedEntry::for_test(3, (12, "Gamma"), "Unrelated", "https://example.com/other"),
+        ]);
+        assert_eq!(list.find_duplicates(), HashMap::from([(0, vec![1])]));
+    }
+
+    #[test]
+    fn entries_with_the_same_title_are_duplicates_only_across_feeds() {
+        let list = list_of(vec![
+            FeedEntry::for_test(1, (10, "Alpha"), "Rust 2.0 Released!", "https://alpha.example/1"),
+            FeedEntry::for_test(2, (11, "Beta"), "rust 2.0: released", "https://beta.example/1"),
+            // Same feed as the first, so a recurring title like this isn't a copy
+            FeedEntry::for_test(3, (10, "Alpha"), "Rust 2.0 released", "https://alpha.example/2"),
+        ]);
+        assert_eq!(list.find_duplicates(), HashMap::from([(0, vec![1])]));
+    }
+
+    fn in_category(mut entry: FeedEntry, category_id: i32) -> FeedEntry {
+        entry.feed.category = Some(Category { id: category_id, title: format!("Category {}", category_id) });
+        return entry;
+    }
+
+    #[test]
+    fn refreshing_a_category_leaves_out_feeds_a_saved_view_excludes() {
+        let view = toml::from_str::<SavedView>("name = \"Just Alpha\"\nfeed_ids = [10]").unwrap();
+        let mut list = FeedEntryList::new(
+            vec![in_category(FeedEntry::for_test(1, (10, "Alpha"), "Old", "https://alpha.example/1"), 5)],
+            FeedListViewType::SavedView(0),
+            ThemeConfig::default(),
+            EntrySort::default(),
+            vec![view],
+            ContentFilter::default()
+        );
+        list.replace_entries_in_scope(EntryScope::Category(5), vec![
+            in_category(FeedEntry::for_test(2, (10, "Alpha"), "New", "https://alpha.example/2"), 5),
+            in_category(FeedEntry::for_test(3, (11, "Beta"), "Not in this view", "https://beta.example/1"), 5),
+        ]);
+        assert_eq!(list.entries.iter().map(|entry| entry.id).collect::<Vec<i32>>(), vec![2]);
+    }
+
+    #[test]
+    fn starring_an_entry_stars_its_duplicates() {
+        let mut list = list_of(vec![
+            FeedEntry::for_test(1, (10, "Alpha"), "Same story", "https://example.com/story"),
+            FeedEntry::for_test(2, (11, "Beta"), "Same story", "https://beta.example/story"),
+        ]);
+        let message = list.toggle_starred(0);
+        assert!(list.entries.iter().all(|entry| entry.starred));
+        match message {
+            Some(Message::Batch(messages)) => assert_eq!(messages.len(), 2),
+            other => panic!("expected a batch of ToggleStarred messages, got {:?}", other)
+        }
+    }
+}
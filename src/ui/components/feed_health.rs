@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+use tui_realm_stdlib::List;
+use tuirealm::{command::{Cmd, CmdResult, Direction, Position}, event::{Key, KeyEvent, KeyModifiers}, props::{Alignment, Color, TableBuilder, TextSpan}, AttrValue, Attribute, Component, Event, MockComponent, State, Sub, SubEventClause};
+
+use crate::{libminiflux::Feed, ui::{ComponentIds, Message, SubClauses, SubscribingComponent}};
+
+/// A feed, along with when it last got a new entry (which Miniflux doesn't report on the feed itself)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FeedHealthReport {
+    pub feed: Feed,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub latest_entry_at: Option<OffsetDateTime>,
+    // Why we couldn't find out when this feed last got a new entry, if asking Miniflux failed
+    #[serde(default)]
+    pub lookup_error: Option<String>,
+}
+
+impl FeedHealthReport {
+    /// Whether this feed is worth showing in the health view: it's failing to parse, or it's been
+    /// quiet for longer than `stale_feed_days` (when that's not 0).
+    pub fn is_broken(&self, stale_feed_days: u32, now: OffsetDateTime) -> bool {
+        if self.has_error() {
+            return true;
+        }
+        if stale_feed_days == 0 {
+            return false;
+        }
+        return match self.latest_entry_at {
+            Some(latest_entry_at) => now - latest_entry_at > Duration::days(stale_feed_days as i64),
+            None => true
+        }
+    }
+
+    fn has_error(&self) -> bool {
+        return self.feed.parsing_error_count > 0 || self.lookup_error.is_some();
+    }
+
+    fn problem(&self) -> String {
+        if let Some(lookup_error) = &self.lookup_error {
+            return format!("Couldn't check for recent entries: {}", lookup_error);
+        }
+        if self.feed.parsing_error_count > 0 {
+            return format!(
+                "{} failed checks: {}",
+                self.feed.parsing_error_count,
+                self.feed.parsing_error_message
+            );
+        }
+        return match self.latest_entry_at {
+            Some(latest_entry_at) => format!("No new entries since {}", latest_entry_at.date()),
+            None => "No entries at all".to_string()
+        }
+    }
+}
+
+/// Lists feeds that are failing to parse, or that haven't had new entries in a while, so that they
+/// can be refreshed, checked on in a browser, or unsubscribed from.
+pub struct FeedHealth {
+    reports: Vec<FeedHealthReport>,
+    component: List,
+}
+
+impl Default for FeedHealth {
+    fn default() -> Self {
+        Self {
+            reports: Vec::default(),
+            component: List::default()
+                .title(" Feed Health ", Alignment::Center)
+                .rows(
+                    TableBuilder::default()
+                        .add_row()
+                        .add_col(TextSpan::from("Loading..."))
+                        .build()
+                )
+                .rewind(true)
+                .scroll(true)
+                .highlighted_str(">> ")
+        }
+    }
+}
+
+impl FeedHealth {
+    fn spans_for_report(report : &FeedHealthReport) -> Vec<TextSpan> {
+        let problem = TextSpan::from(format!(" {} ", report.problem()));
+        let last_checked = match report.feed.checked_at {
+            Some(checked_at) => format!(" (last checked {})", checked_at.date()),
+            None => String::default()
+        };
+        return vec![
+            TextSpan::from(format!(" {} ", report.feed.title)).bold(),
+            TextSpan::from(" »» "),
+            if report.has_error() { problem.fg(Color::Red) } else { problem.fg(Color::Yellow) },
+            TextSpan::from(last_checked).italic()
+        ]
+    }
+
+    fn update_reports(&mut self, reports: Vec<FeedHealthReport>) {
+        self.reports = reports;
+        // Errors first, then the feeds that have been quiet the longest
+        self.reports.sort_by_key(|report| (
+            !report.has_error(),
+            report.latest_entry_at
+        ));
+        let contents =
+            if self.reports.is_empty() {
+                vec![
+                    vec![TextSpan::from("All of your feeds look healthy!")]
+                ]
+            } else {
+                self.reports.iter()
+                    .map(FeedHealth::spans_for_report)
+                    .collect::<Vec<Vec<TextSpan>>>()
+            };
+        self.component.attr(
+            Attribute::Content,
+            AttrValue::Table(contents)
+        );
+    }
+
+    fn selected_feed(&self) -> Option<&Feed> {
+        match self.component.state() {
+            State::One(idx) => self.reports.get(idx.unwrap_usize()).map(|report| &report.feed),
+            _ => None
+        }
+    }
+}
+
+impl SubscribingComponent for FeedHealth {
+    fn subscriptions(component_id : ComponentIds) -> Vec<Sub<ComponentIds, KeyEvent>> {
+        return vec![
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('q'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::unless_prompt_focused()
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('R'),
+                    modifiers: KeyModifiers::SHIFT
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('o'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('d'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('r'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('b'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+        ]
+    }
+}
+
+impl MockComponent for FeedHealth {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            Attribute::Content => {
+                let unwrapped = value.unwrap_payload().unwrap_vec();
+                let updated_reports = unwrapped.iter()
+                    .map(|attr_value| attr_value.clone().unwrap_str())
+                    .map(|json| serde_json::from_str::<FeedHealthReport>(&json).unwrap())
+                    .collect::<Vec<FeedHealthReport>>();
+                self.update_reports(updated_reports)
+            },
+            _ => self.component.attr(attr, value)
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
+
+impl Component<Message, KeyEvent> for FeedHealth {
+    fn on(&mut self, ev: Event<KeyEvent>) -> Option<Message> {
+        let cmd = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Char('q'), .. }) => return Some(Message::AppClose),
+            Event::Keyboard(KeyEvent { code: Key::Char('?'), .. }) => return Some(Message::ShowKeyboardHelp),
+            Event::Keyboard(KeyEvent { code: Key::Char('b'), .. }) => return Some(Message::ShowFeedManager),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => return Some(Message::ShowFeedManager),
+            Event::Keyboard(KeyEvent { code: Key::Char('r'), .. }) => return Some(Message::ShowFeedHealth),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('R'), .. }) => return self.selected_feed().map(|feed|
+                Message::RefreshFeedRequested(feed.id)
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('o'), .. }) => {
+                if let Some(feed) = self.selected_feed() {
+                    let _ = open::that(&feed.site_url);
+                }
+                return None
+            }
+
+            Event::Keyboard(KeyEvent { code: Key::Char('d'), .. }) => return self.selected_feed().map(|feed|
+                Message::ShowConfirmation(
+                    format!("Unsubscribe from \"{}\"?", feed.title),
+                    Box::new(Message::UnsubscribeRequested(feed.id))
+                )
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('j'), .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Down, .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Char('k'), .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::PageUp, .. }) => Cmd::GoTo(Position::Begin),
+            Event::Keyboard(KeyEvent { code: Key::PageDown, .. }) => Cmd::GoTo(Position::End),
+
+            _ => Cmd::None
+        };
+
+        return match self.perform(cmd) {
+            CmdResult::Changed(_) => Some(Message::Tick),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use tui_realm_stdlib::List;
+use tuirealm::{command::{Cmd, CmdResult, Direction, Position}, event::{Key, KeyEvent, KeyModifiers}, props::{Alignment, TableBuilder, TextSpan}, AttrValue, Attribute, Component, Event, MockComponent, State, Sub, SubEventClause};
+
+use crate::{libminiflux::Feed, ui::{ComponentIds, Message, PromptPurpose, SubClauses, SubscribingComponent}};
+
+/// A list of the feeds the user is subscribed to, for adding, renaming, moving, and unsubscribing from them.
+pub struct FeedManager {
+    feeds: Vec<Feed>,
+    component: List,
+}
+
+impl Default for FeedManager {
+    fn default() -> Self {
+        Self {
+            feeds: Vec::default(),
+            component: List::default()
+                .title(" Manage Feeds ", Alignment::Center)
+                .rows(
+                    TableBuilder::default()
+                        .add_row()
+                        .add_col(TextSpan::from("Loading..."))
+                        .build()
+                )
+                .rewind(true)
+                .scroll(true)
+                .highlighted_str(">> ")
+        }
+    }
+}
+
+impl FeedManager {
+    fn spans_for_feed(feed : &Feed) -> Vec<TextSpan> {
+        let category_title = feed.category.as_ref()
+            .map(|category| category.title.clone())
+            .unwrap_or_default();
+        return vec![
+            TextSpan::from(format!("[{}]", category_title)),
+            TextSpan::from(format!(" {} ", feed.title)).bold(),
+            TextSpan::from(" »» "),
+            TextSpan::from(feed.feed_url.to_string()).italic()
+        ]
+    }
+
+    fn update_feeds(&mut self, feeds: Vec<Feed>) {
+        self.feeds = feeds;
+        self.feeds.sort_by_key(|feed| (
+            feed.category.as_ref().map(|category| category.title.to_lowercase()),
+            feed.title.to_lowercase()
+        ));
+        let contents =
+            if self.feeds.is_empty() {
+                vec![
+                    vec![TextSpan::from("You aren't subscribed to any feeds yet. Press a to add one.")]
+                ]
+            } else {
+                self.feeds.iter()
+                    .map(FeedManager::spans_for_feed)
+                    .collect::<Vec<Vec<TextSpan>>>()
+            };
+        self.component.attr(
+            Attribute::Content,
+            AttrValue::Table(contents)
+        );
+    }
+
+    fn selected_feed(&self) -> Option<&Feed> {
+        match self.component.state() {
+            State::One(idx) => self.feeds.get(idx.unwrap_usize()),
+            _ => None
+        }
+    }
+}
+
+impl SubscribingComponent for FeedManager {
+    fn subscriptions(component_id : ComponentIds) -> Vec<Sub<ComponentIds, KeyEvent>> {
+        return vec![
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('q'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::unless_prompt_focused()
+            ),
+
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('a'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('n'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('d'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('m'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('h'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('c'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('r'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+            Sub::new(
+                SubEventClause::Keyboard(KeyEvent {
+                    code: Key::Char('b'),
+                    modifiers: KeyModifiers::NONE
+                }),
+                SubClauses::when_focused(&component_id)
+            ),
+        ]
+    }
+}
+
+impl MockComponent for FeedManager {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        match attr {
+            Attribute::Content => {
+                let unwrapped = value.unwrap_payload().unwrap_vec();
+                let updated_feeds = unwrapped.iter()
+                    .map(|attr_value| attr_value.clone().unwrap_str())
+                    .map(|json| serde_json::from_str::<Feed>(&json).unwrap())
+                    .collect::<Vec<Feed>>();
+                self.update_feeds(updated_feeds)
+            },
+            _ => self.component.attr(attr, value)
+        }
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
+
+impl Component<Message, KeyEvent> for FeedManager {
+    fn on(&mut self, ev: Event<KeyEvent>) -> Option<Message> {
+        let cmd = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Char('q'), .. }) => return Some(Message::AppClose),
+            Event::Keyboard(KeyEvent { code: Key::Char('?'), .. }) => return Some(Message::ShowKeyboardHelp),
+            Event::Keyboard(KeyEvent { code: Key::Char('b'), .. }) => return Some(Message::FeedManagerClosed),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => return Some(Message::FeedManagerClosed),
+            Event::Keyboard(KeyEvent { code: Key::Char('r'), .. }) => return Some(Message::ShowFeedManager),
+            Event::Keyboard(KeyEvent { code: Key::Char('c'), .. }) => return Some(Message::ShowCategoryManager),
+            Event::Keyboard(KeyEvent { code: Key::Char('h'), .. }) => return Some(Message::ShowFeedHealth),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('a'), .. }) => return Some(
+                Message::ShowTextPrompt(
+                    PromptPurpose::AddFeed,
+                    "Subscribe to a feed or website URL".to_string(),
+                    String::default()
+                )
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('n'), .. }) => return self.selected_feed().map(|feed|
+                Message::ShowTextPrompt(
+                    PromptPurpose::RenameFeed(feed.id),
+                    "Rename feed".to_string(),
+                    feed.title.clone()
+                )
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('m'), .. }) => return self.selected_feed().map(|feed|
+                Message::MoveFeedRequested(feed.id)
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('d'), .. }) => return self.selected_feed().map(|feed|
+                Message::ShowConfirmation(
+                    format!("Unsubscribe from \"{}\"?", feed.title),
+                    Box::new(Message::UnsubscribeRequested(feed.id))
+                )
+            ),
+
+            Event::Keyboard(KeyEvent { code: Key::Char('j'), .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Down, .. }) => Cmd::Move(Direction::Down),
+            Event::Keyboard(KeyEvent { code: Key::Char('k'), .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => Cmd::Move(Direction::Up),
+            Event::Keyboard(KeyEvent { code: Key::PageUp, .. }) => Cmd::GoTo(Position::Begin),
+            Event::Keyboard(KeyEvent { code: Key::PageDown, .. }) => Cmd::GoTo(Position::End),
+
+            _ => Cmd::None
+        };
+
+        return match self.perform(cmd) {
+            CmdResult::Changed(_) => Some(Message::Tick),
+            _ => None
+        }
+    }
+}
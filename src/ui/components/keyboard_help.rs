@@ -1,4 +1,4 @@
-use tuirealm::{Props, Sub, event::{KeyEvent, KeyModifiers, Key}, SubClause, MockComponent, Component, State, command::{Cmd, CmdResult}, Event, tui::{widgets::{Table, Row, Block, Borders}, layout::Constraint}, props::{Style, Alignment}};
+use tuirealm::{Props, Sub, event::{KeyEvent, KeyModifiers, Key}, MockComponent, Component, State, command::{Cmd, CmdResult}, Event, tui::{widgets::{Table, Row, Block, Borders}, layout::Constraint}, props::{Style, Alignment}};
 use tuirealm::tui::style::Modifier;
 
 use crate::ui::{SubscribingComponent, ComponentIds, SubClauses, Message, utils::to_window_title};
@@ -25,7 +25,7 @@ impl SubscribingComponent for KeyboardHelp {
                     code: Key::Char('q'),
                     modifiers: KeyModifiers::NONE
                 }),
-                SubClause::Always
+                SubClauses::unless_prompt_focused()
             ),
 
             Sub::new(
@@ -63,12 +63,29 @@ impl MockComponent for KeyboardHelp {
                 Row::new(vec!["", "Page Up", "Jump to beginning"]),
                 Row::new(vec!["", "m", "Mark as read/unread"]),
                 Row::new(vec!["", "a", "Mark All as read"]),
+                Row::new(vec!["", "Shift+A", "Mark this entry's feed, category, or everything as read"]),
+                Row::new(vec!["", "Shift+X", "Delete all read entries from Miniflux (flush history)"]),
+                Row::new(vec!["", "Shift+K", "Mark everything above this entry as read"]),
+                Row::new(vec!["", "Shift+J", "Mark everything below this entry as read"]),
+                Row::new(vec!["", "u", "Undo the last read/unread or star change"]),
                 Row::new(vec!["", "s", "Toggle starred"]),
 				Row::new(vec!["", "e", "Send article to external integrations"]),
+                Row::new(vec!["", "o", "Open in browser"]),
+                Row::new(vec!["", "Space", "Select/unselect this entry"]),
+                Row::new(vec!["", "Shift+V", "Start/stop selecting a range of entries"]),
+                Row::new(vec!["", "Esc", "Clear the selection"]),
                 Row::new(vec!["", "r", "Refresh entries"]),
                 Row::new(vec!["", "Shift+R", "Force-refresh feeds"]),
+                Row::new(vec!["", "f", "Force-refresh this entry's feed"]),
+                Row::new(vec!["", "c", "Force-refresh this entry's category"]),
                 Row::new(vec!["", "Enter", "Read entry"]),
-                Row::new(vec!["", "v", "Swap view (Unread / Starred)"]),
+                Row::new(vec!["", "v", "Swap view (Unread / Starred / All / Recently read / saved views)"]),
+                Row::new(vec!["", "Shift+S", "Sort by date, feed, category, or status"]),
+                Row::new(vec!["", "Shift+D", "Reverse the sort direction"]),
+                Row::new(vec!["", "g", "Group by feed / category / nothing"]),
+                Row::new(vec!["", "z", "Collapse/expand this group"]),
+                Row::new(vec!["", "h", "Show/hide entries hidden by filter rules"]),
+                Row::new(vec!["", "Shift+F", "Manage feeds"]),
                 Row::new(vec![""]),
 
                 Row::new(vec!["", "Read entry view"]).style(Style::default().add_modifier(Modifier::BOLD)),
@@ -84,6 +101,33 @@ impl MockComponent for KeyboardHelp {
                 Row::new(vec!["", "b", "Back to Unread Entries view"]),
                 Row::new(vec!["", "", ""]),
 
+                Row::new(vec!["", "Manage feeds view"]).style(Style::default().add_modifier(Modifier::BOLD)),
+                Row::new(vec!["", "a", "Subscribe to a feed"]),
+                Row::new(vec!["", "n", "Rename feed"]),
+                Row::new(vec!["", "m", "Move feed to another category"]),
+                Row::new(vec!["", "d", "Unsubscribe from feed"]),
+                Row::new(vec!["", "c", "Manage categories"]),
+                Row::new(vec!["", "h", "Show feed health (broken and stale feeds)"]),
+                Row::new(vec!["", "r", "Refresh feed list"]),
+                Row::new(vec!["", "b", "Back to Unread Entries view"]),
+                Row::new(vec!["", "", ""]),
+
+                Row::new(vec!["", "Manage categories view"]).style(Style::default().add_modifier(Modifier::BOLD)),
+                Row::new(vec!["", "a", "Create category"]),
+                Row::new(vec!["", "n", "Rename category"]),
+                Row::new(vec!["", "d", "Delete category (and its feeds)"]),
+                Row::new(vec!["", "r", "Refresh category list"]),
+                Row::new(vec!["", "b", "Back to Manage feeds view"]),
+                Row::new(vec!["", "", ""]),
+
+                Row::new(vec!["", "Feed health view"]).style(Style::default().add_modifier(Modifier::BOLD)),
+                Row::new(vec!["", "Shift+R", "Check feed for new entries now"]),
+                Row::new(vec!["", "o", "Open feed's website in browser"]),
+                Row::new(vec!["", "d", "Unsubscribe from feed"]),
+                Row::new(vec!["", "r", "Refresh feed health list"]),
+                Row::new(vec!["", "b", "Back to Manage feeds view"]),
+                Row::new(vec!["", "", ""]),
+
                 Row::new(vec!["", "Keyboard help view"]).style(Style::default().add_modifier(Modifier::BOLD)),
                 Row::new(vec!["", "Esc", "Close keyboard help"]),
             ];
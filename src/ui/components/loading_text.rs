@@ -1,6 +1,6 @@
-use crate::ui::{ComponentIds, Message, SubscribingComponent};
+use crate::ui::{ComponentIds, Message, SubClauses, SubscribingComponent};
 
-use tuirealm::{Component, MockComponent, State, tui::widgets::Paragraph, Props, props::Style, command::CmdResult, event::{KeyEvent, Key, KeyModifiers}, Event, Sub, SubClause};
+use tuirealm::{Component, MockComponent, State, tui::widgets::Paragraph, Props, props::Style, command::CmdResult, event::{KeyEvent, Key, KeyModifiers}, Event, Sub};
 
 pub struct LoadingText { 
     props: Props
@@ -29,7 +29,7 @@ impl SubscribingComponent for LoadingText {
                     code: Key::Char('q'),
                     modifiers: KeyModifiers::NONE
                 }), 
-                SubClause::Always
+                SubClauses::unless_prompt_focused()
             )
         ]
     }
@@ -3,3 +3,9 @@ pub mod feed_entry_list;
 pub mod read_entry_view;
 pub mod keyboard_help;
 pub mod error_message;
+pub mod feed_manager;
+pub mod text_prompt;
+pub mod choice_prompt;
+pub mod confirmation_prompt;
+pub mod category_manager;
+pub mod feed_health;
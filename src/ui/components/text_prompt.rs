@@ -0,0 +1,83 @@
+use tui_realm_stdlib::Input;
+use tuirealm::{command::{Cmd, CmdResult, Direction, Position}, event::{Key, KeyEvent}, props::{Alignment, BorderType, Borders, InputType}, AttrValue, Attribute, Component, Event, MockComponent, State, StateValue, Sub};
+
+use crate::ui::{ComponentIds, Message, PromptPurpose, SubscribingComponent};
+
+#[derive(Default)]
+pub struct TextPrompt {
+    purpose: Option<PromptPurpose>,
+    component: Input,
+}
+
+impl TextPrompt {
+    pub fn new(purpose: PromptPurpose, title: &str, initial_value: &str) -> Self {
+        Self {
+            purpose: Some(purpose),
+            component: Input::default()
+                .title(format!(" {} (Enter to submit, Esc to cancel) ", title), Alignment::Center)
+                .borders(Borders::default().modifiers(BorderType::Rounded))
+                .input_type(InputType::Text)
+                .value(initial_value)
+        }
+    }
+}
+
+impl SubscribingComponent for TextPrompt {
+    fn subscriptions(_component_id : ComponentIds) -> Vec<Sub<ComponentIds, KeyEvent>> {
+        // Every keypress is meant for the prompt, and it always has focus while it's visible, so
+        // there's nothing to subscribe to.
+        return vec![]
+    }
+}
+
+impl MockComponent for TextPrompt {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
+        self.component.view(frame, area);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.component.query(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.component.attr(attr, value)
+    }
+
+    fn state(&self) -> State {
+        self.component.state()
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        self.component.perform(cmd)
+    }
+}
+
+impl Component<Message, KeyEvent> for TextPrompt {
+    fn on(&mut self, ev: Event<KeyEvent>) -> Option<Message> {
+        let cmd = match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => return Some(Message::PromptClosed),
+            Event::Keyboard(KeyEvent { code: Key::Enter, .. }) => Cmd::Submit,
+            Event::Keyboard(KeyEvent { code: Key::Backspace, .. }) => Cmd::Delete,
+            Event::Keyboard(KeyEvent { code: Key::Delete, .. }) => Cmd::Cancel,
+            Event::Keyboard(KeyEvent { code: Key::Left, .. }) => Cmd::Move(Direction::Left),
+            Event::Keyboard(KeyEvent { code: Key::Right, .. }) => Cmd::Move(Direction::Right),
+            Event::Keyboard(KeyEvent { code: Key::Home, .. }) => Cmd::GoTo(Position::Begin),
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => Cmd::GoTo(Position::End),
+            Event::Keyboard(KeyEvent { code: Key::Char(ch), .. }) => Cmd::Type(ch),
+            _ => Cmd::None
+        };
+
+        return match self.perform(cmd) {
+            CmdResult::Submit(State::One(StateValue::String(value))) => {
+                match &self.purpose {
+                    Some(purpose) if !value.trim().is_empty() => Some(
+                        Message::PromptSubmitted(purpose.clone(), value.trim().to_string())
+                    ),
+                    _ => None
+                }
+            }
+            CmdResult::Changed(_) => Some(Message::Tick),
+            _ => None
+        }
+    }
+}
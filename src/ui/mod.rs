@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use tuirealm::{Update, SubClause, Attribute, AttrValue, event::KeyEvent, Sub};
 
-use crate::{config::ThemeConfig, libminiflux::{Client, FeedEntry, ReadStatus}};
+use crate::{config::Config, libminiflux::{self, Category, Client, EntrySort, Feed, FeedEntry, ReadStatus}};
 
-use self::{model::Model, components::feed_entry_list::FeedListViewType};
+use self::{model::Model, components::{feed_entry_list::FeedListViewType, feed_health::FeedHealthReport}};
 
 pub mod model;
 pub mod components;
@@ -13,21 +14,131 @@ pub enum Message {
     Tick,
     AppClose,
     FeedEntriesReceived(Vec<FeedEntry>),
-    EntrySelected(FeedEntry),
+    EntrySelected(Box<FeedEntry>),
     RefreshRequested(FeedListViewType),
+    AutoRefreshRequested,
+    BackgroundEntriesReceived(FeedListViewType, Vec<FeedEntry>),
     ForceRefreshRequested(FeedListViewType),
     ReadEntryViewClosed,
     ChangeEntryReadStatus(i32, ReadStatus),
+    ChangeEntriesReadStatus(Vec<i32>, ReadStatus),
     ToggleStarred(i32),
     ShowKeyboardHelp,
     HideKeyboardHelp,
     Batch(Vec<Option<Message>>),
-    RequestErrorEncountered(Option<reqwest::StatusCode>, String),
+    RequestErrorEncountered(libminiflux::Error),
     DismissError,
     SaveEntry(i32),
     MarkAllAsRead(Vec<i32>),
+    FlushHistory,
 	FetchOriginalEntryContentsRequested(i32),
 	OriginalEntryContentsReceived(String),
+    ShowFeedManager,
+    FeedManagerClosed,
+    FeedsReceived(Vec<Feed>),
+    UnsubscribeRequested(i32),
+    MoveFeedRequested(i32),
+    ShowCategoryManager,
+    CategoriesReceived(Vec<Category>),
+    DeleteCategoryRequested(i32),
+    ShowFeedHealth,
+    FeedHealthReceived(Vec<FeedHealthReport>),
+    RefreshFeedRequested(i32),
+    RefreshScopeRequested(EntryScope, FeedListViewType),
+    ScopedEntriesReceived(EntryScope, FeedListViewType, Vec<FeedEntry>),
+    ScopedRefreshFailed,
+    MarkScopeAsRead(EntryScope),
+    ShowTextPrompt(PromptPurpose, String, String),
+    ShowChoicePrompt(PromptPurpose, String, Vec<(String, String)>),
+    ShowConfirmation(String, Box<Message>),
+    PromptSubmitted(PromptPurpose, String),
+    PromptClosed,
+    ShowError(String),
+    Undo,
+    SortChanged(EntrySort),
+}
+
+/// A single feed or category (or everything), for actions that can apply to more than what's loaded
+/// into the entry list
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum EntryScope {
+    Feed(i32),
+    Category(i32),
+    All,
+}
+impl EntryScope {
+    pub fn feed_of(entry : &FeedEntry) -> EntryScope {
+        EntryScope::Feed(entry.feed_id)
+    }
+
+    pub fn category_of(entry : &FeedEntry) -> Option<EntryScope> {
+        entry.feed.category.as_ref().map(|category| EntryScope::Category(category.id))
+    }
+
+    pub fn contains(&self, entry : &FeedEntry) -> bool {
+        match self {
+            EntryScope::Feed(feed_id) => entry.feed_id == *feed_id,
+            _ => self.contains_feed(&entry.feed)
+        }
+    }
+
+    pub fn contains_feed(&self, feed : &Feed) -> bool {
+        match self {
+            EntryScope::Feed(feed_id) => feed.id == *feed_id,
+            EntryScope::Category(category_id) => feed.category.as_ref()
+                .is_some_and(|category| category.id == *category_id),
+            EntryScope::All => true
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            EntryScope::Feed(_) => "this feed",
+            EntryScope::Category(_) => "this category",
+            EntryScope::All => "every feed"
+        }
+    }
+}
+
+/// A change to some entries that's been sent to Miniflux, kept around so that it can be undone
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum EntryChange {
+    // These entries were all set to this read status
+    ReadStatus(Vec<i32>, ReadStatus),
+    // These entries were starred if they weren't, and unstarred if they were
+    ToggledStarred(Vec<i32>),
+}
+impl EntryChange {
+    /// The change that puts things back the way they were. This relies on only entries that actually
+    /// changed being recorded (so a ReadStatus change means they all had the other status before).
+    pub fn inverse(&self) -> EntryChange {
+        match self {
+            EntryChange::ReadStatus(entry_ids, status) => EntryChange::ReadStatus(entry_ids.clone(), status.toggle()),
+            EntryChange::ToggledStarred(entry_ids) => EntryChange::ToggledStarred(entry_ids.clone())
+        }
+    }
+
+    pub fn apply_to(&self, entry : &mut FeedEntry) {
+        match self {
+            EntryChange::ReadStatus(entry_ids, status) if entry_ids.contains(&entry.id) => entry.status = status.clone(),
+            EntryChange::ToggledStarred(entry_ids) if entry_ids.contains(&entry.id) => entry.starred = !entry.starred,
+            _ => {}
+        }
+    }
+}
+
+/// What a TextPrompt or ChoicePrompt is asking for, so that the Model knows what to do with the
+/// answer when it comes back in a PromptSubmitted message.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PromptPurpose {
+    AddFeed,
+    ChooseDiscoveredFeed,
+    ChooseNewFeedCategory(String),
+    RenameFeed(i32),
+    MoveFeed(i32),
+    NewCategory,
+    RenameCategory(i32),
+    ChooseScopeToMarkAsRead,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
@@ -37,6 +148,21 @@ pub enum ComponentIds {
     ReadEntry,
     KeyboardHelp,
     ErrorMessage,
+    FeedManager,
+    CategoryManager,
+    FeedHealth,
+    TextPrompt,
+    ChoicePrompt,
+    ConfirmationPrompt,
+}
+
+impl ComponentIds {
+    /// The popups that take over the keyboard while they're open
+    pub const PROMPTS: [ComponentIds; 3] = [
+        ComponentIds::TextPrompt,
+        ComponentIds::ChoicePrompt,
+        ComponentIds::ConfirmationPrompt,
+    ];
 }
 
 trait SubscribingComponent {
@@ -59,18 +185,30 @@ impl SubClauses {
             )
         )
     }
+
+    // For global keys (like q for quit) that shouldn't fire while the user is typing into, or
+    // answering, a prompt.
+    pub fn unless_prompt_focused() -> SubClause<ComponentIds> {
+        let prompt_focused = ComponentIds::PROMPTS.iter()
+            .map(|prompt_id| SubClause::HasAttrValue(
+                prompt_id.clone(),
+                Attribute::Focus,
+                AttrValue::Flag(true)
+            ))
+            .reduce(|a, b| SubClause::Or(Box::new(a), Box::new(b)))
+            .unwrap();
+        SubClause::Not(Box::new(prompt_focused))
+    }
 }
 
 pub struct Ui {
     model: Model,
-	theme_config : ThemeConfig
 }
 impl Ui {
-    pub fn new(miniflux_client : Client, theme_config : ThemeConfig) -> Self {
-        let model = Model::new(miniflux_client, theme_config.clone());
+    pub fn new(miniflux_client : Client, config : Config) -> Self {
+        let model = Model::new(miniflux_client, config);
         return Self {
             model,
-			theme_config
         }
     }
     pub fn run(&mut self) {
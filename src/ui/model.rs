@@ -1,33 +1,64 @@
-use std::time::Duration;
-use crate::{config::ThemeConfig, ui::{SubscribingComponent, components::{keyboard_help::KeyboardHelp, feed_entry_list::FeedListViewType, error_message::ErrorMessage}}};
+use std::{collections::{HashMap, VecDeque}, time::Duration};
+use crate::{config::{Config, SavedView, ThemeConfig}, content_filter::ContentFilter, ui::{SubscribingComponent, EntryChange, EntryScope, PromptPurpose, utils::popup_area, components::{keyboard_help::KeyboardHelp, feed_entry_list::FeedListViewType, error_message::ErrorMessage, feed_manager::FeedManager, feed_health::{FeedHealth, FeedHealthReport}, category_manager::CategoryManager, text_prompt::TextPrompt, choice_prompt::ChoicePrompt, confirmation_prompt::ConfirmationPrompt}}};
 
+use futures::{stream, StreamExt};
+use time::OffsetDateTime;
 use tokio::sync::mpsc;
-use tuirealm::{tui::layout::{Layout, Direction, Constraint}, Application, event::KeyEvent, terminal::TerminalBridge, EventListenerCfg, Update, props::{PropPayload, PropValue}};
+use tuirealm::{tui::{layout::{Layout, Direction, Constraint}, widgets::Clear}, Application, event::KeyEvent, terminal::TerminalBridge, EventListenerCfg, Update, props::{PropPayload, PropValue}};
 
-use crate::{libminiflux::{Client, ReadStatus}, ui::components::{loading_text::LoadingText, feed_entry_list::FeedEntryList, read_entry_view::ReadEntryView}};
+use crate::{libminiflux::{self, Client, EntryFilter, EntrySort, Feed, FeedEntry, FeedModification, ReadStatus}, ui::components::{loading_text::LoadingText, feed_entry_list::FeedEntryList, read_entry_view::ReadEntryView}};
 
 use super::{ComponentIds, Message};
 
 extern crate tuirealm;
 
+// How many read/starred changes can be undone
+const UNDO_HISTORY_LENGTH: usize = 10;
+
+// The most entries loaded into the entry list at once (there's no pagination yet)
+const ENTRY_LIST_LIMIT: i32 = 100;
+
+// Miniflux refreshes categories in the background, so after asking it to, we check this often
+// whether it's done, giving up (and showing whatever's there) after REFRESH_WAIT_TIMEOUT
+const REFRESH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const REFRESH_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct Model {
     pub app: Application<ComponentIds, Message, KeyEvent>,
     pub quit: bool,
     pub redraw: bool,
     pub terminal: TerminalBridge,
     pub miniflux_client: Client,
+    config: Config,
     pub messages_rx : tokio::sync::mpsc::Receiver<Message>,
     messages_tx : tokio::sync::mpsc::Sender<Message>,
     current_view : ComponentIds,
     previous_view : Option<ComponentIds>,
+    // A popup (like a TextPrompt) drawn on top of the current view, and how tall to draw it
+    modal : Option<ComponentIds>,
+    modal_height : u16,
+    feed_list_view_type : FeedListViewType,
+    entry_sort : EntrySort,
+    content_filter : ContentFilter,
+    // Each step is everything that one keypress changed, most recent last
+    undo_history : VecDeque<Vec<EntryChange>>,
+    // While handling a Batch, changes are collected here so that they're undone together
+    undo_batch : Option<Vec<EntryChange>>,
+    // How many single-feed or category refreshes haven't finished yet
+    scoped_refreshes_in_progress : usize,
 }
 
 impl Model { 
-    pub fn new(miniflux_client : Client, theme_config : ThemeConfig) -> Self {
+    pub fn new(miniflux_client : Client, config : Config) -> Self {
         let (messages_tx, messages_rx) = mpsc::channel::<Message>(32);
+        // The rules were already checked when the config file was loaded
+        let content_filter = ContentFilter::new(&config.filters).unwrap_or_default();
 
         let mut instance = Self {
-            app: Self::init_app(theme_config),
+            app: Self::init_app(config.theme.clone(), config.sort, config.views.clone(), content_filter.clone()),
+            entry_sort: config.sort,
+            content_filter,
+            config,
             quit: false,
             redraw: false,
             terminal: TerminalBridge::new().expect("Cannot initialize terminal"),
@@ -35,9 +66,18 @@ impl Model {
             messages_tx,
             messages_rx,
             current_view: ComponentIds::LoadingText,
-            previous_view: None
+            previous_view: None,
+            modal: None,
+            modal_height: 0,
+            feed_list_view_type: FeedListViewType::UnreadEntries,
+            undo_history: VecDeque::new(),
+            undo_batch: None,
+            scoped_refreshes_in_progress: 0
         };
         instance.update(Some(Message::RefreshRequested(FeedListViewType::UnreadEntries)));
+        if instance.config.auto_refresh_secs > 0 {
+            instance.start_auto_refresh(Duration::from_secs(instance.config.auto_refresh_secs));
+        }
         return instance
     }
 
@@ -50,12 +90,17 @@ impl Model {
                     .constraints([Constraint::Percentage(100)].as_ref()) 
                     .split(f.size());
                 self.app.view(&self.current_view.clone(), f, chunks[0]);
+                if let Some(modal) = &self.modal {
+                    let popup = popup_area(chunks[0], self.modal_height);
+                    f.render_widget(Clear, popup);
+                    self.app.view(modal, f, popup);
+                }
             }).is_ok()
         );
-        let _ = self.app.active(&self.current_view);
+        let _ = self.app.active(self.modal.as_ref().unwrap_or(&self.current_view));
     }
 
-    fn init_app(theme_config : ThemeConfig) -> Application<ComponentIds, Message, KeyEvent> {
+    fn init_app(theme_config : ThemeConfig, entry_sort : EntrySort, saved_views : Vec<SavedView>, content_filter : ContentFilter) -> Application<ComponentIds, Message, KeyEvent> {
         let mut app: Application<ComponentIds, Message, KeyEvent> = Application::init(
             EventListenerCfg::default()
                 .default_input_listener(Duration::from_millis(20))
@@ -74,7 +119,7 @@ impl Model {
         assert!(
             app.mount(
                 ComponentIds::FeedEntryList, 
-                Box::new(FeedEntryList::new(Vec::default(), FeedListViewType::UnreadEntries, theme_config.to_owned())),
+                Box::new(FeedEntryList::new(Vec::default(), FeedListViewType::UnreadEntries, theme_config.to_owned(), entry_sort, saved_views, content_filter)),
                 FeedEntryList::subscriptions(ComponentIds::FeedEntryList)
             ).is_ok()
         );
@@ -103,6 +148,54 @@ impl Model {
 			).is_ok()
 		);
 
+        assert!(
+            app.mount(
+                ComponentIds::FeedManager,
+                Box::new(FeedManager::default()),
+                FeedManager::subscriptions(ComponentIds::FeedManager)
+            ).is_ok()
+        );
+
+        assert!(
+            app.mount(
+                ComponentIds::CategoryManager,
+                Box::new(CategoryManager::default()),
+                CategoryManager::subscriptions(ComponentIds::CategoryManager)
+            ).is_ok()
+        );
+
+        assert!(
+            app.mount(
+                ComponentIds::FeedHealth,
+                Box::new(FeedHealth::default()),
+                FeedHealth::subscriptions(ComponentIds::FeedHealth)
+            ).is_ok()
+        );
+
+        // The prompts get remounted with their real contents each time they're shown, but they
+        // need to be mounted from the start so that subscriptions can check whether they're focused.
+        assert!(
+            app.mount(
+                ComponentIds::TextPrompt,
+                Box::new(TextPrompt::default()),
+                TextPrompt::subscriptions(ComponentIds::TextPrompt)
+            ).is_ok()
+        );
+        assert!(
+            app.mount(
+                ComponentIds::ChoicePrompt,
+                Box::new(ChoicePrompt::default()),
+                ChoicePrompt::subscriptions(ComponentIds::ChoicePrompt)
+            ).is_ok()
+        );
+        assert!(
+            app.mount(
+                ComponentIds::ConfirmationPrompt,
+                Box::new(ConfirmationPrompt::default()),
+                ConfirmationPrompt::subscriptions(ComponentIds::ConfirmationPrompt)
+            ).is_ok()
+        );
+
         return app;
     }
 
@@ -117,6 +210,33 @@ impl Model {
         });
     }
 
+    fn change_read_statuses(&self, entry_ids : Vec<i32>, new_status : ReadStatus) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.change_entries_read_status(entry_ids, new_status).await {
+                Ok(_) => {}
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    // Marks anything that a "mark_read" filter rule matches as read, both on the server and in the
+    // entries that are about to be shown
+    fn apply_mark_read_rules(&self, mut entries : Vec<FeedEntry>) -> Vec<FeedEntry> {
+        let entry_ids = entries.iter_mut()
+            .filter(|entry| entry.status == ReadStatus::Unread && self.content_filter.marks_read(entry))
+            .map(|entry| {
+                entry.status = ReadStatus::Read;
+                entry.id
+            })
+            .collect::<Vec<i32>>();
+        if !entry_ids.is_empty() {
+            self.change_read_statuses(entry_ids, ReadStatus::Read);
+        }
+        return entries;
+    }
+
     fn toggle_starred(&mut self, entry_id : i32) {
         let miniflux_client = self.miniflux_client.clone();
 		let messages_tx = self.messages_tx.clone();
@@ -139,6 +259,51 @@ impl Model {
 		});
 	}
 
+    fn record_change(&mut self, change : EntryChange) {
+        match &mut self.undo_batch {
+            Some(changes) => changes.push(change),
+            None => self.push_undo_step(vec![change])
+        }
+    }
+
+    fn push_undo_step(&mut self, changes : Vec<EntryChange>) {
+        self.undo_history.push_back(changes);
+        if self.undo_history.len() > UNDO_HISTORY_LENGTH {
+            self.undo_history.pop_front();
+        }
+    }
+
+    // Sends the inverse of the most recent step to Miniflux (without recording it, so that undoing
+    // twice goes further back rather than redoing), and updates the entry list to match.
+    fn undo(&mut self) {
+        let Some(changes) = self.undo_history.pop_back() else {
+            return;
+        };
+        let inverses = changes.iter()
+            .rev()
+            .map(EntryChange::inverse)
+            .collect::<Vec<EntryChange>>();
+        for inverse in &inverses {
+            match inverse {
+                EntryChange::ReadStatus(entry_ids, status) => self.change_read_statuses(entry_ids.clone(), status.clone()),
+                EntryChange::ToggledStarred(entry_ids) => {
+                    for entry_id in entry_ids {
+                        self.toggle_starred(*entry_id);
+                    }
+                }
+            }
+        }
+        assert!(
+            self.app.attr(
+                &ComponentIds::FeedEntryList,
+                tuirealm::Attribute::Custom("entry_changes"),
+                tuirealm::AttrValue::Payload(
+                    PropPayload::One(PropValue::Str(serde_json::to_string(&inverses).unwrap()))
+                )
+            ).is_ok()
+        );
+    }
+
 	fn mark_all_as_read(&self, entry_ids: Vec<i32>) {
 		let miniflux_client = self.miniflux_client.clone();
 		let messages_tx = self.messages_tx.clone();
@@ -163,16 +328,141 @@ impl Model {
         });
     }
 
+    fn entry_filters(&self, view_type : FeedListViewType, scope : Option<EntryScope>) -> Vec<EntryFilter> {
+        let sort = view_type.sort(self.entry_sort, &self.config.views);
+        return view_type.filters(scope, &self.config.views, OffsetDateTime::now_utc())
+            .into_iter()
+            .map(|filter| EntryFilter { sort, ..filter })
+            .collect();
+    }
+
+    // Has Miniflux check just one feed or category for new entries, then reloads only that part of the list
+    fn refresh_scope(&self, scope : EntryScope, view_type : FeedListViewType) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        let filters = self.entry_filters(view_type, Some(scope));
+        tokio::spawn(async move {
+            // A single feed is refreshed before Miniflux responds, but categories (and everything) are
+            // only queued up, so for those we note when each feed was last checked, to wait for it to change.
+            let checked_before = match scope {
+                EntryScope::Feed(_) => None,
+                _ => miniflux_client.get_feeds().await.ok()
+                    .map(|feeds| Self::last_checked_times(&feeds, scope))
+            };
+            let refreshed = match scope {
+                EntryScope::Feed(feed_id) => miniflux_client.refresh_feed(feed_id).await,
+                EntryScope::Category(category_id) => miniflux_client.refresh_category(category_id).await,
+                EntryScope::All => miniflux_client.refresh_all_feeds().await
+            };
+            if let Err(e) = refreshed {
+                let _ = messages_tx.send(Message::ScopedRefreshFailed).await;
+                return Self::handle_error_message(e, messages_tx).await;
+            }
+            if let Some(checked_before) = checked_before {
+                Self::wait_for_feeds_to_be_checked(&miniflux_client, scope, checked_before).await;
+            }
+            match miniflux_client.get_entries_matching_any(&filters, ENTRY_LIST_LIMIT).await {
+                Ok(entries) => {
+                    let _ = messages_tx.send(Message::ScopedEntriesReceived(scope, view_type, entries)).await;
+                }
+                Err(e) => {
+                    let _ = messages_tx.send(Message::ScopedRefreshFailed).await;
+                    Self::handle_error_message(e, messages_tx).await
+                }
+            }
+        });
+    }
+
+    fn finish_scoped_refresh(&mut self) {
+        self.scoped_refreshes_in_progress = self.scoped_refreshes_in_progress.saturating_sub(1);
+        self.show_scoped_refresh_progress();
+    }
+
+    // Refreshing a category can take a while, so the entry list says when one is still going
+    fn show_scoped_refresh_progress(&mut self) {
+        assert!(
+            self.app.attr(
+                &ComponentIds::FeedEntryList,
+                tuirealm::Attribute::Custom("refreshing"),
+                tuirealm::AttrValue::Flag(self.scoped_refreshes_in_progress > 0)
+            ).is_ok()
+        );
+    }
+
+    fn last_checked_times(feeds : &[Feed], scope : EntryScope) -> HashMap<i32, Option<OffsetDateTime>> {
+        return feeds.iter()
+            .filter(|feed| scope.contains_feed(feed))
+            .map(|feed| (feed.id, feed.checked_at))
+            .collect();
+    }
+
+    // Polls until every feed in the scope has been checked since checked_before, or until
+    // REFRESH_WAIT_TIMEOUT is up, since a feed that's timing out can take a long while.
+    async fn wait_for_feeds_to_be_checked(
+        miniflux_client : &Client,
+        scope : EntryScope,
+        checked_before : HashMap<i32, Option<OffsetDateTime>>
+    ) {
+        let deadline = tokio::time::Instant::now() + REFRESH_WAIT_TIMEOUT;
+        while tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(REFRESH_POLL_INTERVAL).await;
+            let feeds = match miniflux_client.get_feeds().await {
+                Ok(feeds) => feeds,
+                Err(_) => return
+            };
+            let checked_now = Self::last_checked_times(&feeds, scope);
+            if checked_before.iter().all(|(feed_id, before)| checked_now.get(feed_id) != Some(before)) {
+                return;
+            }
+        }
+    }
+
+    // Deletes every read entry from the server, then reloads the list
+    fn flush_history(&self) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        let view_type = self.feed_list_view_type;
+        tokio::spawn(async move {
+            match miniflux_client.flush_history().await {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::RefreshRequested(view_type)).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    // Marks everything in a feed, category, or account as read on the server (not just what's loaded),
+    // then reloads the list
+    fn mark_scope_as_read(&self, scope : EntryScope) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        let view_type = self.feed_list_view_type;
+        tokio::spawn(async move {
+            let marked = match scope {
+                EntryScope::Feed(feed_id) => miniflux_client.mark_feed_as_read(feed_id).await,
+                EntryScope::Category(category_id) => miniflux_client.mark_category_as_read(category_id).await,
+                EntryScope::All => match miniflux_client.get_me().await {
+                    Ok(user) => miniflux_client.mark_user_as_read(user.id).await,
+                    Err(e) => Err(e)
+                }
+            };
+            match marked {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::RefreshRequested(view_type)).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
     fn do_refresh(&mut self, view_type : FeedListViewType) {
         let miniflux_client = self.miniflux_client.clone();
         let messages_tx = self.messages_tx.clone();
+        let filters = self.entry_filters(view_type, None);
         tokio::spawn(async move {
             // TODO: pagination
-            let entries = match view_type {
-                FeedListViewType::UnreadEntries => miniflux_client.get_unread_entries(100, 0).await,
-                FeedListViewType::StarredEntries => miniflux_client.get_starred_entries(100, 0).await, 
-            };
-			match entries {
+			match miniflux_client.get_entries_matching_any(&filters, ENTRY_LIST_LIMIT).await {
 				Ok(updated_entries) => {
 					let _ = messages_tx.send(
 						Message::FeedEntriesReceived(updated_entries)
@@ -183,6 +473,36 @@ impl Model {
         });
     }
 
+    // Asks for an auto-refresh every so often, for as long as the app is running
+    fn start_auto_refresh(&self, interval : Duration) {
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick is immediate, and the entries were only just loaded
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                if messages_tx.send(Message::AutoRefreshRequested).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    // Like do_refresh, but without the loading screen, and leaving the cursor and selection alone.
+    // Failures are ignored rather than popping up an error every few minutes; pressing r will
+    // report them.
+    fn do_background_refresh(&self, view_type : FeedListViewType) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        let filters = self.entry_filters(view_type, None);
+        tokio::spawn(async move {
+            if let Ok(entries) = miniflux_client.get_entries_matching_any(&filters, ENTRY_LIST_LIMIT).await {
+                let _ = messages_tx.send(Message::BackgroundEntriesReceived(view_type, entries)).await;
+            }
+        });
+    }
+
 	fn fetch_original_content(&self, entry_id: i32) {
 		let miniflux_client = self.miniflux_client.clone();
 		let messages_tx = self.messages_tx.clone();
@@ -198,9 +518,263 @@ impl Model {
 		});
 	}
 
-	async fn handle_error_message(e : reqwest::Error, messages_tx : tokio::sync::mpsc::Sender<Message>) {
+    fn load_feeds(&self) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.get_feeds().await {
+                Ok(feeds) => {
+                    let _ = messages_tx.send(Message::FeedsReceived(feeds)).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn discover_feeds(&self, url : String) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.discover(url.clone()).await {
+                Ok(discovered_feeds) if discovered_feeds.is_empty() => {
+                    let _ = messages_tx.send(
+                        Message::ShowError(format!("Couldn't find any feeds at {}", url))
+                    ).await;
+                }
+                Ok(discovered_feeds) if discovered_feeds.len() == 1 => {
+                    Self::ask_for_category(
+                        miniflux_client,
+                        PromptPurpose::ChooseNewFeedCategory(discovered_feeds[0].url.clone()),
+                        "Add it to which category?",
+                        messages_tx
+                    ).await
+                }
+                Ok(discovered_feeds) => {
+                    let options = discovered_feeds.into_iter()
+                        .map(|feed| (format!("{} ({}) »» {}", feed.title, feed.feed_type, feed.url), feed.url))
+                        .collect();
+                    let _ = messages_tx.send(
+                        Message::ShowChoicePrompt(
+                            PromptPurpose::ChooseDiscoveredFeed,
+                            "Which feed?".to_string(),
+                            options
+                        )
+                    ).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn choose_category(&self, purpose : PromptPurpose, question : &'static str) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(Self::ask_for_category(miniflux_client, purpose, question, messages_tx));
+    }
+
+    // Shows a ChoicePrompt listing the user's categories; the chosen category's id comes back in a 
+    // PromptSubmitted message with the given purpose.
+    async fn ask_for_category(miniflux_client : Client, purpose : PromptPurpose, question : &str, messages_tx : tokio::sync::mpsc::Sender<Message>) {
+        match miniflux_client.get_categories().await {
+            Ok(categories) => {
+                let options = categories.into_iter()
+                    .map(|category| (category.title, category.id.to_string()))
+                    .collect();
+                let _ = messages_tx.send(
+                    Message::ShowChoicePrompt(
+                        purpose,
+                        question.to_string(),
+                        options
+                    )
+                ).await;
+            }
+            Err(e) => Self::handle_error_message(e, messages_tx).await
+        }
+    }
+
+    fn subscribe(&self, feed_url : String, category_id : i32) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.create_feed(feed_url, category_id).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::ShowFeedManager).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn rename_feed(&self, feed_id : i32, title : String) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            let modification = FeedModification {
+                title: Some(title),
+                ..FeedModification::default()
+            };
+            match miniflux_client.update_feed(feed_id, modification).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::ShowFeedManager).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn move_feed(&self, feed_id : i32, category_id : i32) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            let modification = FeedModification {
+                category_id: Some(category_id),
+                ..FeedModification::default()
+            };
+            match miniflux_client.update_feed(feed_id, modification).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::ShowFeedManager).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn load_categories(&self) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.get_categories().await {
+                Ok(categories) => {
+                    let _ = messages_tx.send(Message::CategoriesReceived(categories)).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn create_category(&self, title : String) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.create_category(title).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::ShowCategoryManager).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn rename_category(&self, category_id : i32, title : String) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.update_category(category_id, title).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::ShowCategoryManager).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn delete_category(&self, category_id : i32) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        tokio::spawn(async move {
+            match miniflux_client.delete_category(category_id).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(Message::ShowCategoryManager).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn load_feed_health(&self) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        let stale_feed_days = self.config.stale_feed_days;
+        tokio::spawn(async move {
+            let feeds = match miniflux_client.get_feeds().await {
+                Ok(feeds) => feeds,
+                Err(e) => return Self::handle_error_message(e, messages_tx).await
+            };
+            // Miniflux doesn't tell us when a feed last had a new entry, so we have to ask feed-by-feed.
+            // One feed failing shouldn't hide the rest of the report, so failures show up as their own rows.
+            let reports = stream::iter(feeds)
+                .map(|feed| {
+                    let miniflux_client = miniflux_client.clone();
+                    async move {
+                        match miniflux_client.get_latest_entry_date(feed.id).await {
+                            Ok(latest_entry_at) => FeedHealthReport { feed, latest_entry_at, lookup_error: None },
+                            Err(e) => FeedHealthReport { feed, latest_entry_at: None, lookup_error: Some(e.to_string()) }
+                        }
+                    }
+                })
+                .buffer_unordered(8)
+                .collect::<Vec<FeedHealthReport>>()
+                .await;
+            let now = OffsetDateTime::now_utc();
+            let broken = reports.into_iter()
+                .filter(|report| report.is_broken(stale_feed_days, now))
+                .collect();
+            let _ = messages_tx.send(Message::FeedHealthReceived(broken)).await;
+        });
+    }
+
+    // After changing a feed from one of the feed-management views, reload whichever one we're on
+    fn reload_feed_view_message(&self) -> Message {
+        return match self.current_view {
+            ComponentIds::FeedHealth => Message::ShowFeedHealth,
+            _ => Message::ShowFeedManager
+        }
+    }
+
+    fn refresh_feed(&self, feed_id : i32) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        let reload_message = self.reload_feed_view_message();
+        tokio::spawn(async move {
+            match miniflux_client.refresh_feed(feed_id).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(reload_message).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn unsubscribe(&self, feed_id : i32) {
+        let miniflux_client = self.miniflux_client.clone();
+        let messages_tx = self.messages_tx.clone();
+        let reload_message = self.reload_feed_view_message();
+        tokio::spawn(async move {
+            match miniflux_client.delete_feed(feed_id).await {
+                Ok(_) => {
+                    let _ = messages_tx.send(reload_message).await;
+                }
+                Err(e) => Self::handle_error_message(e, messages_tx).await
+            }
+        });
+    }
+
+    fn show_error(&mut self, text : String) {
+        self.modal = None;
+        self.previous_view = Some(self.current_view.clone());
+        assert!(
+            self.app.attr(
+                &ComponentIds::ErrorMessage,
+                tuirealm::Attribute::Content,
+                tuirealm::AttrValue::String(text)
+            ).is_ok()
+        );
+        self.current_view = ComponentIds::ErrorMessage;
+    }
+
+	async fn handle_error_message(e : libminiflux::Error, messages_tx : tokio::sync::mpsc::Sender<Message>) {
 		let _ = messages_tx.send(
-			Message::RequestErrorEncountered(e.status(), e.to_string())
+			Message::RequestErrorEncountered(e)
 		).await;
 	}
 
@@ -212,10 +786,19 @@ impl Update<Message> for Model {
             self.redraw = true;
             match msg {
                 Message::Batch(msgs) => {
+                    let outermost_batch = self.undo_batch.is_none();
+                    if outermost_batch {
+                        self.undo_batch = Some(vec![]);
+                    }
                     let results: Vec<Option<Message>> = msgs.iter()
                         .map(|msg| self.update(msg.to_owned()))
                         .filter(Option::is_some)
                         .collect();
+                    if outermost_batch {
+                        if let Some(changes) = self.undo_batch.take().filter(|changes| !changes.is_empty()) {
+                            self.push_undo_step(changes);
+                        }
+                    }
                     return match results.len() {
                         0 => None,
                         1 => results[0].to_owned(),
@@ -230,12 +813,14 @@ impl Update<Message> for Model {
                 }
 
                 Message::RefreshRequested(view_type) => {
+                    self.feed_list_view_type = view_type;
                     self.current_view = ComponentIds::LoadingText;
                     self.do_refresh(view_type);
                     return Some(Message::Tick)
                 }
 
                 Message::FeedEntriesReceived(entries) => {
+                    let entries = self.apply_mark_read_rules(entries);
                     let serialized_entries = entries.iter()
                         .map(|e| serde_json::to_string(e).unwrap())
                         .map(|json| PropValue::Str(json))
@@ -253,6 +838,69 @@ impl Update<Message> for Model {
                     return Some(Message::Tick)
                 }
 
+                // Skipped while a normal refresh is already loading
+                Message::AutoRefreshRequested if self.current_view == ComponentIds::LoadingText => return None,
+                Message::AutoRefreshRequested => {
+                    self.do_background_refresh(self.feed_list_view_type);
+                    return Some(Message::Tick)
+                }
+
+                // If the view was switched since this refresh started, these entries belong to the old one
+                Message::BackgroundEntriesReceived(view_type, _) if view_type != self.feed_list_view_type => return None,
+                Message::BackgroundEntriesReceived(_, entries) => {
+                    let entries = self.apply_mark_read_rules(entries);
+                    assert!(
+                        self.app.attr(
+                            &ComponentIds::FeedEntryList,
+                            tuirealm::Attribute::Custom("background_entries"),
+                            tuirealm::AttrValue::Payload(
+                                PropPayload::One(PropValue::Str(serde_json::to_string(&entries).unwrap()))
+                            )
+                        ).is_ok()
+                    );
+                    return Some(Message::Tick)
+                }
+
+                Message::RefreshScopeRequested(scope, view_type) => {
+                    self.scoped_refreshes_in_progress += 1;
+                    self.show_scoped_refresh_progress();
+                    self.refresh_scope(scope, view_type);
+                    return Some(Message::Tick)
+                }
+
+                Message::ScopedRefreshFailed => {
+                    self.finish_scoped_refresh();
+                    return Some(Message::Tick)
+                }
+
+                Message::MarkScopeAsRead(scope) => {
+                    self.mark_scope_as_read(scope);
+                    return Some(Message::Tick)
+                }
+
+                // Like BackgroundEntriesReceived, these belong to the old view if it was switched since
+                Message::ScopedEntriesReceived(_, view_type, _) if view_type != self.feed_list_view_type => {
+                    self.finish_scoped_refresh();
+                    return Some(Message::Tick)
+                }
+                Message::ScopedEntriesReceived(scope, _, entries) => {
+                    self.finish_scoped_refresh();
+                    let entries = self.apply_mark_read_rules(entries);
+                    assert!(
+                        self.app.attr(
+                            &ComponentIds::FeedEntryList,
+                            tuirealm::Attribute::Custom("scoped_entries"),
+                            tuirealm::AttrValue::Payload(
+                                PropPayload::Tup2((
+                                    PropValue::Str(serde_json::to_string(&scope).unwrap()),
+                                    PropValue::Str(serde_json::to_string(&entries).unwrap())
+                                ))
+                            )
+                        ).is_ok()
+                    );
+                    return Some(Message::Tick)
+                }
+
                 Message::ForceRefreshRequested(view_type) => {
                     self.current_view = ComponentIds::LoadingText;
                     self.force_refresh_feeds(view_type);
@@ -260,11 +908,19 @@ impl Update<Message> for Model {
                 }
                 
                 Message::ChangeEntryReadStatus(entry_id, new_status) => {
+                    self.record_change(EntryChange::ReadStatus(vec![entry_id], new_status.clone()));
                     self.change_read_status(entry_id, new_status);
                     return Some(Message::Tick)
                 }
 
+                Message::ChangeEntriesReadStatus(entry_ids, new_status) => {
+                    self.record_change(EntryChange::ReadStatus(entry_ids.clone(), new_status.clone()));
+                    self.change_read_statuses(entry_ids, new_status);
+                    return Some(Message::Tick)
+                }
+
                 Message::ToggleStarred(entry_id) => {
+                    self.record_change(EntryChange::ToggledStarred(vec![entry_id]));
                     self.toggle_starred(entry_id);
                     return Some(Message::Tick)
                 }
@@ -307,22 +963,16 @@ impl Update<Message> for Model {
 
                 }
 
-				Message::RequestErrorEncountered(status_code_maybe, err_string) => {
-					let status_code_str = match status_code_maybe {
-						None => "UNKNOWN".to_owned(),
-						Some(v) => v.to_string()
+				Message::RequestErrorEncountered(err) => {
+					let text = match err.hint(&self.config) {
+						Some(hint) => format!("{}\n\nHint: {}", err, hint),
+						None => err.to_string()
 					};
-					self.previous_view = Some(self.current_view.clone());
-					assert!(
-						self.app.attr(
-							&ComponentIds::ErrorMessage,
-							tuirealm::Attribute::Content,
-							tuirealm::AttrValue::String(
-								format!("Error {status_code_str}: {err_string}")
-							)
-						).is_ok()
-					);
-					self.current_view = ComponentIds::ErrorMessage;
+					self.show_error(text);
+					return Some(Message::Tick);
+				}
+				Message::ShowError(text) => {
+					self.show_error(text);
 					return Some(Message::Tick);
 				}
 				Message::DismissError => {
@@ -340,10 +990,37 @@ impl Update<Message> for Model {
 				}
 
 				Message::MarkAllAsRead(entry_ids) => {
+					// This comes after a confirmation, so the entry list hasn't marked them yet
+					let change = EntryChange::ReadStatus(entry_ids.clone(), ReadStatus::Read);
+					assert!(
+						self.app.attr(
+							&ComponentIds::FeedEntryList,
+							tuirealm::Attribute::Custom("entry_changes"),
+							tuirealm::AttrValue::Payload(
+								PropPayload::One(PropValue::Str(serde_json::to_string(&vec![change.clone()]).unwrap()))
+							)
+						).is_ok()
+					);
+					self.record_change(change);
 					self.mark_all_as_read(entry_ids);
 					return Some(Message::Tick);
 				}
 
+				Message::FlushHistory => {
+					self.flush_history();
+					return Some(Message::Tick);
+				}
+
+				Message::SortChanged(entry_sort) => {
+					self.entry_sort = entry_sort;
+					return Some(Message::RefreshRequested(self.feed_list_view_type));
+				}
+
+				Message::Undo => {
+					self.undo();
+					return Some(Message::Tick);
+				}
+
 				Message::FetchOriginalEntryContentsRequested(entry_id) => {
 					self.fetch_original_content(entry_id);
 					return Some(Message::Tick);
@@ -360,6 +1037,190 @@ impl Update<Message> for Model {
 					return Some(Message::Tick);
 				}
 
+                Message::ShowFeedManager => {
+                    // Only show the loading screen on the way in; reloads after a change happen in place.
+                    if self.current_view != ComponentIds::FeedManager {
+                        self.current_view = ComponentIds::LoadingText;
+                    }
+                    self.load_feeds();
+                    return Some(Message::Tick);
+                }
+
+                Message::FeedsReceived(feeds) => {
+                    let serialized_feeds = feeds.iter()
+                        .map(|f| serde_json::to_string(f).unwrap())
+                        .map(PropValue::Str)
+                        .collect::<Vec<PropValue>>();
+                    assert!(
+                        self.app.attr(
+                            &ComponentIds::FeedManager,
+                            tuirealm::Attribute::Content,
+                            tuirealm::AttrValue::Payload(
+                                PropPayload::Vec(serialized_feeds)
+                            )
+                        ).is_ok()
+                    );
+                    self.current_view = ComponentIds::FeedManager;
+                    return Some(Message::Tick);
+                }
+
+                Message::FeedManagerClosed => {
+                    // Subscriptions may have changed, so the entry list may be out of date
+                    return Some(Message::RefreshRequested(self.feed_list_view_type));
+                }
+
+                Message::UnsubscribeRequested(feed_id) => {
+                    self.unsubscribe(feed_id);
+                    return Some(Message::Tick);
+                }
+
+                Message::MoveFeedRequested(feed_id) => {
+                    self.choose_category(PromptPurpose::MoveFeed(feed_id), "Move it to which category?");
+                    return Some(Message::Tick);
+                }
+
+                Message::ShowCategoryManager => {
+                    if self.current_view != ComponentIds::CategoryManager {
+                        self.current_view = ComponentIds::LoadingText;
+                    }
+                    self.load_categories();
+                    return Some(Message::Tick);
+                }
+
+                Message::CategoriesReceived(categories) => {
+                    let serialized_categories = categories.iter()
+                        .map(|c| serde_json::to_string(c).unwrap())
+                        .map(PropValue::Str)
+                        .collect::<Vec<PropValue>>();
+                    assert!(
+                        self.app.attr(
+                            &ComponentIds::CategoryManager,
+                            tuirealm::Attribute::Content,
+                            tuirealm::AttrValue::Payload(
+                                PropPayload::Vec(serialized_categories)
+                            )
+                        ).is_ok()
+                    );
+                    self.current_view = ComponentIds::CategoryManager;
+                    return Some(Message::Tick);
+                }
+
+                Message::DeleteCategoryRequested(category_id) => {
+                    self.delete_category(category_id);
+                    return Some(Message::Tick);
+                }
+
+                Message::ShowFeedHealth => {
+                    if self.current_view != ComponentIds::FeedHealth {
+                        self.current_view = ComponentIds::LoadingText;
+                    }
+                    self.load_feed_health();
+                    return Some(Message::Tick);
+                }
+
+                Message::FeedHealthReceived(reports) => {
+                    let serialized_reports = reports.iter()
+                        .map(|r| serde_json::to_string(r).unwrap())
+                        .map(PropValue::Str)
+                        .collect::<Vec<PropValue>>();
+                    assert!(
+                        self.app.attr(
+                            &ComponentIds::FeedHealth,
+                            tuirealm::Attribute::Content,
+                            tuirealm::AttrValue::Payload(
+                                PropPayload::Vec(serialized_reports)
+                            )
+                        ).is_ok()
+                    );
+                    self.current_view = ComponentIds::FeedHealth;
+                    return Some(Message::Tick);
+                }
+
+                Message::RefreshFeedRequested(feed_id) => {
+                    self.refresh_feed(feed_id);
+                    return Some(Message::Tick);
+                }
+
+                Message::ShowTextPrompt(purpose, title, initial_value) => {
+                    assert!(
+                        self.app.remount(
+                            ComponentIds::TextPrompt,
+                            Box::new(TextPrompt::new(purpose, &title, &initial_value)),
+                            TextPrompt::subscriptions(ComponentIds::TextPrompt)
+                        ).is_ok()
+                    );
+                    self.modal = Some(ComponentIds::TextPrompt);
+                    self.modal_height = 3;
+                    return Some(Message::Tick);
+                }
+
+                Message::ShowChoicePrompt(purpose, title, options) => {
+                    self.modal_height = ChoicePrompt::height_for(options.len());
+                    assert!(
+                        self.app.remount(
+                            ComponentIds::ChoicePrompt,
+                            Box::new(ChoicePrompt::new(purpose, &title, options)),
+                            ChoicePrompt::subscriptions(ComponentIds::ChoicePrompt)
+                        ).is_ok()
+                    );
+                    self.modal = Some(ComponentIds::ChoicePrompt);
+                    return Some(Message::Tick);
+                }
+
+                Message::ShowConfirmation(_, on_confirm) if self.config.skip_confirmations => {
+                    return Some(*on_confirm);
+                }
+
+                Message::ShowConfirmation(question, on_confirm) => {
+                    assert!(
+                        self.app.remount(
+                            ComponentIds::ConfirmationPrompt,
+                            Box::new(ConfirmationPrompt::new(&question, *on_confirm)),
+                            ConfirmationPrompt::subscriptions(ComponentIds::ConfirmationPrompt)
+                        ).is_ok()
+                    );
+                    self.modal = Some(ComponentIds::ConfirmationPrompt);
+                    self.modal_height = ConfirmationPrompt::HEIGHT;
+                    return Some(Message::Tick);
+                }
+
+                Message::PromptClosed => {
+                    self.modal = None;
+                    return Some(Message::Tick);
+                }
+
+                Message::PromptSubmitted(purpose, value) => {
+                    self.modal = None;
+                    match purpose {
+                        PromptPurpose::ChooseScopeToMarkAsRead => {
+                            if let Ok(scope) = serde_json::from_str::<EntryScope>(&value) {
+                                return Some(Message::ShowConfirmation(
+                                    format!("Mark every entry in {} as read?", scope.description()),
+                                    Box::new(Message::MarkScopeAsRead(scope))
+                                ));
+                            }
+                        }
+                        PromptPurpose::AddFeed => self.discover_feeds(value),
+                        PromptPurpose::ChooseDiscoveredFeed => self.choose_category(
+                            PromptPurpose::ChooseNewFeedCategory(value),
+                            "Add it to which category?"
+                        ),
+                        PromptPurpose::ChooseNewFeedCategory(feed_url) => {
+                            if let Ok(category_id) = value.parse::<i32>() {
+                                self.subscribe(feed_url, category_id);
+                            }
+                        }
+                        PromptPurpose::RenameFeed(feed_id) => self.rename_feed(feed_id, value),
+                        PromptPurpose::MoveFeed(feed_id) => {
+                            if let Ok(category_id) = value.parse::<i32>() {
+                                self.move_feed(feed_id, category_id);
+                            }
+                        }
+                        PromptPurpose::NewCategory => self.create_category(value),
+                        PromptPurpose::RenameCategory(category_id) => self.rename_category(category_id, value),
+                    }
+                    return Some(Message::Tick);
+                }
 
                 _ => {}
             }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use tuirealm::{tui::{style::ParseColorError, text::Span}, props::{Color, Style, TextModifiers, TextSpan}};
+use tuirealm::{tui::{layout::Rect, style::ParseColorError, text::Span}, props::{Color, Style, TextModifiers, TextSpan}};
 
 use crate::{config::ThemeConfig, libminiflux::{FeedEntry, ReadStatus}};
 
@@ -72,3 +72,15 @@ pub fn to_window_title(text : &str) -> Span {
         Style::default().add_modifier(TextModifiers::BOLD)
     )
 }
+
+/// Where to draw a popup of the given height: centered on top of `area`, and most of its width.
+pub fn popup_area(area : Rect, height : u16) -> Rect {
+    let width = area.width.saturating_mul(4) / 5;
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height
+    )
+}